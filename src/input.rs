@@ -0,0 +1,566 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use ggez::{
+    glam::{vec2, Vec2},
+    input::{
+        gamepad::gilrs::{Axis, Button},
+        keyboard::KeyCode,
+        mouse::MouseButton,
+    },
+    Context,
+};
+
+/// Bindings used when the bindings file can't be loaded.
+const DEFAULT_BINDINGS: &str = include_str!("../resources/bindings.txt");
+
+/// The actions of the visualizer which can be bound to an input.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    Drag,
+    ResetZoom,
+    Recenter,
+    FollowMode,
+    Rotate,
+    Select,
+    SaveBookmark,
+    NextBookmark,
+    Pause,
+    Step,
+    Screenshot,
+    Quit,
+    ToggleParticles,
+    ToggleGrid,
+    ToggleTrail,
+    CycleHeatmap,
+    ToggleElevation,
+    CycleFog,
+    ToggleFilter,
+    ToggleLegend,
+    ToggleFrontier,
+    ToggleTeleports,
+    ToggleSpotlight,
+    SpotlightCloser,
+    SpotlightFarther,
+    TrailShorter,
+    TrailLonger,
+}
+
+/// Names of the actions in the bindings file.
+const ACTIONS: [(&str, Action); 33] = [
+    ("pan_left", Action::PanLeft),
+    ("pan_right", Action::PanRight),
+    ("pan_up", Action::PanUp),
+    ("pan_down", Action::PanDown),
+    ("zoom_in", Action::ZoomIn),
+    ("zoom_out", Action::ZoomOut),
+    ("drag", Action::Drag),
+    ("reset_zoom", Action::ResetZoom),
+    ("recenter", Action::Recenter),
+    ("follow_mode", Action::FollowMode),
+    ("rotate", Action::Rotate),
+    ("select", Action::Select),
+    ("save_bookmark", Action::SaveBookmark),
+    ("next_bookmark", Action::NextBookmark),
+    ("pause", Action::Pause),
+    ("step", Action::Step),
+    ("screenshot", Action::Screenshot),
+    ("quit", Action::Quit),
+    ("toggle_particles", Action::ToggleParticles),
+    ("toggle_grid", Action::ToggleGrid),
+    ("toggle_trail", Action::ToggleTrail),
+    ("cycle_heatmap", Action::CycleHeatmap),
+    ("toggle_elevation", Action::ToggleElevation),
+    ("cycle_fog", Action::CycleFog),
+    ("toggle_filter", Action::ToggleFilter),
+    ("toggle_legend", Action::ToggleLegend),
+    ("toggle_frontier", Action::ToggleFrontier),
+    ("toggle_teleports", Action::ToggleTeleports),
+    ("toggle_spotlight", Action::ToggleSpotlight),
+    ("spotlight_closer", Action::SpotlightCloser),
+    ("spotlight_farther", Action::SpotlightFarther),
+    ("trail_shorter", Action::TrailShorter),
+    ("trail_longer", Action::TrailLonger),
+];
+
+/// Keys which can be bound, named as in the bindings file.
+const KEYS: [KeyCode; 102] = [
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Escape,
+    KeyCode::Snapshot,
+    KeyCode::Pause,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::Left,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Back,
+    KeyCode::Return,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::NumpadSubtract,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadEnter,
+    KeyCode::Apostrophe,
+    KeyCode::Backslash,
+    KeyCode::Comma,
+    KeyCode::Equals,
+    KeyCode::Grave,
+    KeyCode::LBracket,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::RBracket,
+    KeyCode::Semicolon,
+    KeyCode::Slash,
+    KeyCode::LAlt,
+    KeyCode::LControl,
+    KeyCode::LShift,
+    KeyCode::RAlt,
+    KeyCode::RControl,
+    KeyCode::RShift,
+    KeyCode::Plus,
+    KeyCode::Asterisk,
+    KeyCode::Colon,
+    KeyCode::Underline,
+];
+
+/// Gamepad buttons which can be bound, named as in the bindings file.
+const BUTTONS: [Button; 19] = [
+    Button::South,
+    Button::East,
+    Button::North,
+    Button::West,
+    Button::C,
+    Button::Z,
+    Button::LeftTrigger,
+    Button::LeftTrigger2,
+    Button::RightTrigger,
+    Button::RightTrigger2,
+    Button::Select,
+    Button::Start,
+    Button::Mode,
+    Button::LeftThumb,
+    Button::RightThumb,
+    Button::DPadUp,
+    Button::DPadDown,
+    Button::DPadLeft,
+    Button::DPadRight,
+];
+
+/// Gamepad axes which can be bound, named as in the bindings file.
+const AXES: [Axis; 8] = [
+    Axis::LeftStickX,
+    Axis::LeftStickY,
+    Axis::LeftZ,
+    Axis::RightStickX,
+    Axis::RightStickY,
+    Axis::RightZ,
+    Axis::DPadX,
+    Axis::DPadY,
+];
+
+/// An input which can be bound to an action.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Input {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    WheelUp,
+    WheelDown,
+    Button(Button),
+    /// The positive (true) or the negative (false) side of a gamepad axis.
+    Axis(Axis, bool),
+}
+
+/// The input map binds the inputs to the actions of the visualizer and keeps track of the
+/// inputs being held, so that continuous actions like panning can be read on every frame.
+pub struct InputMap {
+    bindings: HashMap<Input, Action>,
+    held: HashSet<Input>,
+    axes: HashMap<Axis, f32>,
+    dead_zone: f32,
+    pan_sensitivity: f32,
+    stick_pan_sensitivity: f32,
+    zoom_sensitivity: f32,
+    wheel_sensitivity: f32,
+}
+
+impl InputMap {
+    /// The constructor parses the bindings from the given text, with a binding or a setting on
+    /// every line. Empty lines and lines starting with # are ignored.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut input_map = Self {
+            bindings: HashMap::new(),
+            held: HashSet::new(),
+            axes: HashMap::new(),
+            dead_zone: 0.15,
+            pan_sensitivity: 10.0,
+            stick_pan_sensitivity: 5.0,
+            zoom_sensitivity: 5.0,
+            wheel_sensitivity: 25.0,
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| format!("line {}: {}", number + 1, message);
+
+            let (name, value) = line
+                .split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .ok_or_else(|| error(format!("expected `name = value`, found `{}`", line)))?;
+
+            let setting = match name {
+                "dead_zone" => Some(&mut input_map.dead_zone),
+                "pan_sensitivity" => Some(&mut input_map.pan_sensitivity),
+                "stick_pan_sensitivity" => Some(&mut input_map.stick_pan_sensitivity),
+                "zoom_sensitivity" => Some(&mut input_map.zoom_sensitivity),
+                "wheel_sensitivity" => Some(&mut input_map.wheel_sensitivity),
+                _ => None,
+            };
+
+            if let Some(setting) = setting {
+                *setting = value
+                    .parse()
+                    .map_err(|_| error(format!("`{}` is not a number", value)))?;
+                continue;
+            }
+
+            let action = ACTIONS
+                .iter()
+                .find(|(action_name, _)| *action_name == name)
+                .map(|(_, action)| *action)
+                .ok_or_else(|| error(format!("unknown action `{}`", name)))?;
+
+            for input in value.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+                let input = parse_input(input).map_err(error)?;
+                input_map.bindings.insert(input, action);
+            }
+        }
+
+        if !(0.0..1.0).contains(&input_map.dead_zone) {
+            return Err(String::from("dead_zone must be between 0.0 and 1.0"));
+        }
+
+        Ok(input_map)
+    }
+
+    /// The function loads the bindings from the bindings.txt file of the resources, falling
+    /// back to the default bindings if the file is missing or invalid.
+    pub fn load(ctx: &Context) -> Self {
+        let text = ctx.fs.open("/bindings.txt").and_then(|mut file| {
+            let mut text = String::new();
+            file.read_to_string(&mut text)?;
+            Ok(text)
+        });
+
+        match text
+            .map_err(|error| error.to_string())
+            .and_then(|text| Self::parse(&text))
+        {
+            Ok(input_map) => input_map,
+            Err(error) => {
+                println!(
+                    "Using the default bindings, the bindings file can't be loaded: {}",
+                    error
+                );
+                Self::parse(DEFAULT_BINDINGS).expect("the default bindings are valid")
+            }
+        }
+    }
+
+    /// The function marks the input as held and returns the action bound to it.
+    pub fn press(&mut self, input: Input) -> Option<Action> {
+        self.held.insert(input);
+        self.bindings.get(&input).copied()
+    }
+
+    /// The function marks the input as released.
+    pub fn release(&mut self, input: Input) {
+        self.held.remove(&input);
+    }
+
+    /// The function returns the action bound to the input, without holding it.
+    pub fn action(&self, input: Input) -> Option<Action> {
+        self.bindings.get(&input).copied()
+    }
+
+    /// The function sets the current value of a gamepad axis.
+    pub fn set_axis(&mut self, axis: Axis, value: f32) {
+        self.axes.insert(axis, value);
+    }
+
+    /// The function returns how strongly the action is requested, between 0.0 and 1.0.
+    /// Held keys and buttons request the action fully, while axes are scaled outside of the
+    /// dead zone.
+    pub fn value(&self, action: Action) -> f32 {
+        self.value_from(action, false)
+            .max(self.value_from(action, true))
+    }
+
+    /// The function returns how strongly the action is requested by the axes bound to it, or by
+    /// the other inputs bound to it when `axes` is false, between 0.0 and 1.0.
+    fn value_from(&self, action: Action, axes: bool) -> f32 {
+        self.bindings
+            .iter()
+            .filter(|(input, bound)| **bound == action && matches!(input, Input::Axis(..)) == axes)
+            .map(|(input, _)| match input {
+                Input::Axis(axis, positive) => {
+                    let value = self.axes.get(axis).copied().unwrap_or(0.0);
+                    let value = if *positive { value } else { -value };
+
+                    ((value - self.dead_zone) / (1.0 - self.dead_zone)).clamp(0.0, 1.0)
+                }
+                input if self.held.contains(input) => 1.0,
+                _ => 0.0,
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The function returns whether the action is bound to one of the held inputs.
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held
+            .iter()
+            .any(|input| self.bindings.get(input) == Some(&action))
+    }
+
+    /// The function returns the offset the camera is panned by in the current frame.
+    pub fn pan_offset(&self) -> Vec2 {
+        vec2(
+            self.pan_value(Action::PanRight) - self.pan_value(Action::PanLeft),
+            self.pan_value(Action::PanUp) - self.pan_value(Action::PanDown),
+        )
+    }
+
+    /// The function returns the pixels the camera is panned by in the current frame for the
+    /// action. The axes have their own sensitivity, as sticks are held longer than keys.
+    fn pan_value(&self, action: Action) -> f32 {
+        (self.value_from(action, false) * self.pan_sensitivity)
+            .max(self.value_from(action, true) * self.stick_pan_sensitivity)
+    }
+
+    /// The function returns the zoom applied in the current frame.
+    pub fn zoom(&self) -> f32 {
+        (self.value(Action::ZoomIn) - self.value(Action::ZoomOut)) * self.zoom_sensitivity
+    }
+
+    /// The function returns the fraction of the axes ignored around their rest position.
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// The function returns the zoom applied by a step of the mouse wheel.
+    pub fn wheel_sensitivity(&self) -> f32 {
+        self.wheel_sensitivity
+    }
+}
+
+/// The function parses an input written as `kind:name`.
+fn parse_input(input: &str) -> Result<Input, String> {
+    let unknown = || format!("unknown input `{}`", input);
+    let (kind, name) = input.split_once(':').ok_or_else(unknown)?;
+
+    match kind {
+        "key" => KEYS
+            .iter()
+            .find(|key| format!("{:?}", key) == name)
+            .map(|key| Input::Key(*key)),
+        "mouse" => match name {
+            "Left" => Some(Input::Mouse(MouseButton::Left)),
+            "Right" => Some(Input::Mouse(MouseButton::Right)),
+            "Middle" => Some(Input::Mouse(MouseButton::Middle)),
+            _ => None,
+        },
+        "wheel" => match name {
+            "Up" => Some(Input::WheelUp),
+            "Down" => Some(Input::WheelDown),
+            _ => None,
+        },
+        "button" => BUTTONS
+            .iter()
+            .find(|button| format!("{:?}", button) == name)
+            .map(|button| Input::Button(*button)),
+        "axis" => {
+            let (name, positive) = if let Some(name) = name.strip_suffix('+') {
+                (name, true)
+            } else if let Some(name) = name.strip_suffix('-') {
+                (name, false)
+            } else {
+                return Err(unknown());
+            };
+
+            AXES.iter()
+                .find(|axis| format!("{:?}", axis) == name)
+                .map(|axis| Input::Axis(*axis, positive))
+        }
+        _ => None,
+    }
+    .ok_or_else(unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_are_valid() {
+        let input_map = InputMap::parse(DEFAULT_BINDINGS).unwrap();
+
+        assert_eq!(
+            input_map.action(Input::Key(KeyCode::W)),
+            Some(Action::PanUp)
+        );
+        assert_eq!(
+            input_map.action(Input::Axis(Axis::LeftStickX, false)),
+            Some(Action::PanLeft)
+        );
+        assert_eq!(input_map.dead_zone(), 0.15);
+        assert_eq!(input_map.wheel_sensitivity(), 25.0);
+    }
+
+    #[test]
+    fn settings_override_the_defaults() {
+        let input_map = InputMap::parse(
+            "dead_zone = 0.3\npan_sensitivity = 20\nstick_pan_sensitivity = 8\nwheel_sensitivity = 5",
+        )
+        .unwrap();
+
+        assert_eq!(input_map.dead_zone(), 0.3);
+        assert_eq!(input_map.pan_sensitivity, 20.0);
+        assert_eq!(input_map.stick_pan_sensitivity, 8.0);
+        assert_eq!(input_map.zoom_sensitivity, 5.0);
+        assert_eq!(input_map.wheel_sensitivity(), 5.0);
+    }
+
+    #[test]
+    fn unknown_actions_are_rejected() {
+        assert_eq!(
+            InputMap::parse("# comment\n\nfly = key:F").err(),
+            Some(String::from("line 3: unknown action `fly`"))
+        );
+    }
+
+    #[test]
+    fn bad_inputs_are_rejected() {
+        for text in [
+            "pan_left",
+            "pan_left = key:Nope",
+            "pan_left = key",
+            "pan_left = joystick:Left",
+            "pan_left = axis:LeftStickX",
+            "pan_left = axis:LeftStickX*",
+            "pan_left = axis:é",
+            "pan_left = axis:",
+            "pan_sensitivity = fast",
+        ] {
+            assert!(InputMap::parse(text).is_err(), "`{}` is accepted", text);
+        }
+
+        assert_eq!(
+            parse_input("axis:LeftStickX-"),
+            Ok(Input::Axis(Axis::LeftStickX, false))
+        );
+    }
+
+    #[test]
+    fn dead_zone_must_be_a_fraction() {
+        assert!(InputMap::parse("dead_zone = 0.0").is_ok());
+        assert!(InputMap::parse("dead_zone = 0.99").is_ok());
+        assert!(InputMap::parse("dead_zone = 1.0").is_err());
+        assert!(InputMap::parse("dead_zone = -0.1").is_err());
+        assert!(InputMap::parse("dead_zone = NaN").is_err());
+    }
+
+    #[test]
+    fn axes_pan_with_their_own_sensitivity() {
+        let mut input_map = InputMap::parse(
+            "dead_zone = 0.2\npan_sensitivity = 10\nstick_pan_sensitivity = 5\n\
+             pan_right = key:D, axis:LeftStickX+",
+        )
+        .unwrap();
+
+        // Inside the dead zone, the stick doesn't pan.
+        input_map.set_axis(Axis::LeftStickX, 0.1);
+        assert_eq!(input_map.pan_offset(), Vec2::ZERO);
+
+        input_map.set_axis(Axis::LeftStickX, 1.0);
+        assert_eq!(input_map.pan_offset(), vec2(5.0, 0.0));
+
+        // A held key pans at its full sensitivity.
+        input_map.press(Input::Key(KeyCode::D));
+        assert_eq!(input_map.pan_offset(), vec2(10.0, 0.0));
+    }
+}
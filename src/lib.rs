@@ -1,3 +1,4 @@
-pub mod gamepad;
+pub mod input;
+pub mod session;
 pub mod visualizer;
 pub mod wrapper;
@@ -1,17 +1,19 @@
 use std::{cell::RefCell, collections::VecDeque, env, path::PathBuf, rc::Rc};
 
 use ai_builder::{get_world_generator_parameters as builder_get_world_generator_parameters, BuilderAi};
-use gamepad::GamePad;
 use ggez::{
-    event::{Axis, EventHandler},
+    event::{Axis, Button, EventHandler},
     glam::vec2,
     graphics::FontData,
+    input::{keyboard::KeyInput, mouse::MouseButton},
 };
+use input::{Action, Input, InputMap};
 use midgard::{params::{WorldGeneratorParameters, ContentsRadii}, WorldGenerator};
-use robot::MyRobot;
+use robot::{MyRobot, SPOTLIGHT_DISTANCE};
 use rusteze_ai_artemisia::{
     get_world_generator_parameters as artemis_get_world_generator_parameters, ArtemisIA,
 };
+use session::Session;
 use visualizer::Visualizer;
 
 use robotics_lib::{
@@ -21,8 +23,9 @@ use robotics_lib::{
 };
 use wrapper::UiWrapper;
 
-mod gamepad;
+mod input;
 mod robot;
+mod session;
 mod visualizer;
 mod wrapper;
 
@@ -31,19 +34,96 @@ const WORLD_SCALE: f64 = 0.5;
 
 struct State {
     visualizer: Visualizer,
-    gamepad: GamePad,
+    input: InputMap,
+    session: Session,
+    paused: bool,
+    step: bool,
+}
+
+impl State {
+    /// Moves the cursor in the center of the window, where the gamepad points at the map.
+    fn use_gamepad_cursor(&mut self, ctx: &ggez::Context) {
+        let size = ctx.gfx.window().inner_size();
+
+        self.visualizer
+            .set_cursor(Some(vec2(size.width as f32, size.height as f32) * 0.5));
+    }
+
+    /// Performs the discrete action bound to a pressed input.
+    fn perform(&mut self, ctx: &mut ggez::Context, action: Action) -> Result<(), ggez::GameError> {
+        match action {
+            Action::Quit => ctx.request_quit(),
+            Action::ResetZoom => self.visualizer.reset_scale(ctx),
+            Action::Recenter => self.visualizer.recenter(),
+            Action::FollowMode => self.visualizer.cycle_follow_mode()?,
+            Action::Rotate => self.visualizer.rotate(ctx)?,
+            Action::Select => self.visualizer.select(ctx)?,
+            Action::SaveBookmark => {
+                let (tile_center, scale) = self.visualizer.bookmark(ctx);
+                let name = self.session.add_bookmark(tile_center, scale).name.clone();
+
+                // The bookmark is kept for this run even if the session can't be saved.
+                let message = match self.session.save(ctx) {
+                    Ok(()) => format!("{} saved", name),
+                    Err(error) => format!("{} can't be saved in the session: {}", name, error),
+                };
+                self.visualizer.show_message(&message)?;
+            }
+            Action::NextBookmark => match self.session.next_bookmark() {
+                Some(bookmark) => self.visualizer.show_bookmark(ctx, bookmark)?,
+                None => self.visualizer.show_message("No bookmarks saved yet")?,
+            },
+            Action::Pause => {
+                self.paused = !self.paused;
+                self.visualizer.show_message(if self.paused {
+                    "Simulation paused"
+                } else {
+                    "Simulation resumed"
+                })?;
+            }
+            Action::Step => {
+                // Stepping pauses the simulation, so the tick can be looked at.
+                self.paused = true;
+                self.step = true;
+                self.visualizer.show_message("Simulation stepped by a tick")?;
+            }
+            Action::Screenshot => self.visualizer.screenshot(ctx)?,
+            Action::ToggleParticles => self.visualizer.toggle_particles()?,
+            Action::ToggleGrid => self.visualizer.toggle_grid()?,
+            Action::ToggleTrail => self.visualizer.toggle_trail()?,
+            Action::CycleHeatmap => self.visualizer.cycle_heatmap()?,
+            Action::ToggleElevation => self.visualizer.toggle_elevation()?,
+            Action::CycleFog => self.visualizer.cycle_fog()?,
+            Action::ToggleFilter => self.visualizer.toggle_filter()?,
+            Action::ToggleLegend => self.visualizer.toggle_legend()?,
+            Action::ToggleFrontier => self.visualizer.toggle_frontier()?,
+            Action::ToggleTeleports => self.visualizer.toggle_teleports()?,
+            Action::ToggleSpotlight => self.visualizer.toggle_spotlight()?,
+            Action::SpotlightCloser => self.visualizer.add_spotlight_distance(-1)?,
+            Action::SpotlightFarther => self.visualizer.add_spotlight_distance(1)?,
+            Action::TrailShorter => self.visualizer.add_trail_length(-50)?,
+            Action::TrailLonger => self.visualizer.add_trail_length(50)?,
+            // Continuous actions are read on every frame.
+            Action::PanLeft
+            | Action::PanRight
+            | Action::PanUp
+            | Action::PanDown
+            | Action::ZoomIn
+            | Action::ZoomOut
+            | Action::Drag => {}
+        }
+
+        Ok(())
+    }
 }
 
 impl EventHandler for State {
     fn update(&mut self, ctx: &mut ggez::Context) -> Result<(), ggez::GameError> {
-        if ctx.time.ticks() % 50 == 0 {
-            if self
-                .visualizer
-                .event_queue()
-                .borrow_mut()
-                .pop_front()
-                .is_some()
-            {
+        if (ctx.time.ticks() % 50 == 0 && !self.paused) || self.step {
+            self.step = false;
+
+            // The events are popped by the visualizer while it handles them.
+            if !self.visualizer.event_queue().borrow().is_empty() {
                 self.visualizer.handle_event(&ctx.gfx)?;
             } else {
                 if let Err(error) = self.visualizer.next_tick() {
@@ -55,11 +135,13 @@ impl EventHandler for State {
             }
         }
 
-        // Sends user input to the visualizer
-        self.visualizer
-            .add_offset(self.gamepad.get_leftstick_offset());
+        // Pans and zooms the camera while the bound inputs are held.
+        self.visualizer.add_offset(self.input.pan_offset());
+        self.visualizer.add_scale(ctx, self.input.zoom());
+
+        // Advances the animations of the visualizer.
         self.visualizer
-            .add_scale(ctx, self.gamepad.get_rightstick_offset().y);
+            .animate(ctx, ctx.time.delta().as_secs_f32())?;
 
         Ok(())
     }
@@ -69,19 +151,160 @@ impl EventHandler for State {
         Ok(())
     }
 
+    fn resize_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        _width: f32,
+        _height: f32,
+    ) -> Result<(), ggez::GameError> {
+        self.visualizer.resize(ctx);
+        Ok(())
+    }
+
     fn gamepad_axis_event(
         &mut self,
-        _ctx: &mut ggez::Context,
-        axis: ggez::input::gamepad::gilrs::Axis,
+        ctx: &mut ggez::Context,
+        axis: Axis,
         value: f32,
         _id: ggez::event::GamepadId,
     ) -> Result<(), ggez::GameError> {
-        if axis == Axis::LeftStickY {
-            self.gamepad.set_leftstick_y_offset(value);
-        } else if axis == Axis::LeftStickX {
-            self.gamepad.set_leftstick_x_offset(value);
-        } else if axis == Axis::RightStickY {
-            self.gamepad.set_rightstick_y_offset(value);
+        // Resting sticks don't take the cursor away from the mouse.
+        if value.abs() > self.input.dead_zone() {
+            self.use_gamepad_cursor(ctx);
+        }
+        self.input.set_axis(axis, value);
+        Ok(())
+    }
+
+    fn gamepad_button_down_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        button: Button,
+        _id: ggez::event::GamepadId,
+    ) -> Result<(), ggez::GameError> {
+        self.use_gamepad_cursor(ctx);
+        if let Some(action) = self.input.press(Input::Button(button)) {
+            self.perform(ctx, action)?;
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        button: Button,
+        _id: ggez::event::GamepadId,
+    ) -> Result<(), ggez::GameError> {
+        self.input.release(Input::Button(button));
+        Ok(())
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        x: f32,
+        y: f32,
+        dx: f32,
+        dy: f32,
+    ) -> Result<(), ggez::GameError> {
+        self.visualizer.set_cursor(Some(vec2(x, y)));
+
+        // Drags the map while an input bound to dragging is held.
+        if self.input.is_held(Action::Drag) {
+            self.visualizer.add_offset(vec2(-dx, dy));
+        }
+        Ok(())
+    }
+
+    fn mouse_wheel_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        _x: f32,
+        y: f32,
+    ) -> Result<(), ggez::GameError> {
+        let position = ctx.mouse.position();
+        let input = if y > 0.0 {
+            Input::WheelUp
+        } else {
+            Input::WheelDown
+        };
+
+        // Zooms toward the cursor for every step of the wheel.
+        let zoom = match self.input.action(input) {
+            Some(Action::ZoomIn) => self.input.wheel_sensitivity(),
+            Some(Action::ZoomOut) => -self.input.wheel_sensitivity(),
+            Some(action) => return self.perform(ctx, action),
+            None => return Ok(()),
+        };
+
+        self.visualizer
+            .add_scale_at(y.abs() * zoom, vec2(position.x, position.y));
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> Result<(), ggez::GameError> {
+        if button == MouseButton::Left {
+            self.visualizer.click(ctx, vec2(x, y))?;
+        }
+        if let Some(action) = self.input.press(Input::Mouse(button)) {
+            self.perform(ctx, action)?;
+        }
+        Ok(())
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> Result<(), ggez::GameError> {
+        self.input.release(Input::Mouse(button));
+        Ok(())
+    }
+
+    fn mouse_enter_or_leave(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        entered: bool,
+    ) -> Result<(), ggez::GameError> {
+        if !entered {
+            self.visualizer.set_cursor(None);
+        }
+        Ok(())
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        input: KeyInput,
+        repeated: bool,
+    ) -> Result<(), ggez::GameError> {
+        if let Some(keycode) = input.keycode {
+            let action = self.input.press(Input::Key(keycode));
+
+            // Holding a key doesn't repeat its action.
+            if let (Some(action), false) = (action, repeated) {
+                self.perform(ctx, action)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn key_up_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        input: KeyInput,
+    ) -> Result<(), ggez::GameError> {
+        if let Some(keycode) = input.keycode {
+            self.input.release(Input::Key(keycode));
         }
 
         Ok(())
@@ -163,7 +386,7 @@ fn main() {
 
     // Generates the world.
     let mut world_generator = WorldGenerator::new(params);
-    let (map, spawn_point, _weather, _max_score, _score_table) = world_generator.gen();
+    let (map, spawn_point, weather, max_score, score_table) = world_generator.gen();
 
     // Creates the shared states.
     let world_rc: Rc<RefCell<Option<Vec<Vec<Option<Tile>>>>>> = Rc::new(RefCell::new(None));
@@ -192,14 +415,32 @@ fn main() {
         map_rc.clone(),
         spawn_point,
         4.0,
+        weather,
+        max_score,
+        score_table,
     );
 
     // Centers the visualizer on the spawn point at start.
-    visualizer.set_center(&ctx.gfx, vec2(spawn_point.1 as f32, spawn_point.0 as f32));
+    visualizer.set_center_now(&ctx.gfx, vec2(spawn_point.1 as f32, spawn_point.0 as f32));
+
+    // Previews the spotlight with the same distance used by the robot, which is known only for
+    // our own robot.
+    if robot_name == "myrobot" {
+        visualizer.set_spotlight_distance(SPOTLIGHT_DISTANCE);
+    }
+
+    // Loads the bindings of the inputs, falling back to the default ones.
+    let input = InputMap::load(&ctx);
+
+    // Loads the bookmarks saved by the previous run of the same robot, if any.
+    let session = Session::load(&ctx, &robot_name);
 
     let state = State {
         visualizer,
-        gamepad: GamePad::new(),
+        input,
+        session,
+        paused: false,
+        step: false,
     };
 
     // Runs the event loop.
@@ -9,6 +9,9 @@ use rust_eze_spotlight::Spotlight;
 use rust_eze_tomtom::{plain::PlainContent, TomTom};
 use ui_lib::RunnableUi;
 
+/// Distance of the tiles illuminated by the spotlight on every tick.
+pub const SPOTLIGHT_DISTANCE: usize = 10;
+
 pub struct MyRobot {
     pub runnable_ui: Box<dyn RunnableUi>,
     pub robot: Robot,
@@ -22,7 +25,7 @@ impl MyRobot {
 
 impl Runnable for MyRobot {
     fn process_tick(&mut self, world: &mut robotics_lib::world::World) {
-        Spotlight::illuminate(self, world, 10).unwrap_or_else(|error| {
+        Spotlight::illuminate(self, world, SPOTLIGHT_DISTANCE).unwrap_or_else(|error| {
             println!("Spotlight error: {:?}", error);
         });
 
@@ -0,0 +1,251 @@
+use std::io::{Read, Write};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    Context, GameError, GameResult,
+};
+
+/// The function returns the path of the session file of the given robot, in the user data
+/// directory. Every robot explores its own world, so it keeps its own bookmarks.
+fn session_path(robot_name: &str) -> String {
+    format!("/session-{}.txt", robot_name)
+}
+
+/// Header written at the top of the session file.
+const HEADER: &str = "\
+# Session of the visualizer, rewritten every time a bookmark is saved.
+#
+# Every bookmark is a camera position, written as:
+#     bookmark = <name>, <column>, <row>, <zoom>
+# The column and the row are the tile in the center of the window. Names can be edited freely.
+";
+
+/// A named camera position, which can be restored later.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bookmark {
+    pub name: String,
+    /// The (column, row) of the tile in the center of the window.
+    pub tile_center: Vec2,
+    pub scale: f32,
+}
+
+/// The session keeps the state of the visualizer which outlives a run, that is the camera
+/// bookmarks saved by the user.
+/// A session file which can't be loaded is never overwritten, so the bookmarks it holds aren't
+/// lost because of a typo.
+#[derive(Default)]
+pub struct Session {
+    robot_name: String,
+    load_error: Option<String>,
+    bookmarks: Vec<Bookmark>,
+    current: Option<usize>,
+}
+
+impl Session {
+    /// The constructor parses the session from the given text, with a bookmark on every line.
+    /// Empty lines and lines starting with # are ignored.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut session = Self::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| format!("line {}: {}", number + 1, message);
+
+            let (name, value) = line
+                .split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .ok_or_else(|| error(format!("expected `name = value`, found `{}`", line)))?;
+
+            if name != "bookmark" {
+                return Err(error(format!("unknown entry `{}`", name)));
+            }
+
+            // The name goes first and may contain commas, so the numbers are split from the end.
+            let mut fields = value.rsplitn(4, ',').map(str::trim);
+            let mut number = || {
+                let field = fields.next().unwrap_or_default();
+                field
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .ok_or_else(|| error(format!("`{}` is not a number", field)))
+            };
+
+            let scale = number()?;
+            let row = number()?;
+            let col = number()?;
+            let name = fields
+                .next()
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| error(String::from("the bookmark has no name")))?;
+
+            session.bookmarks.push(Bookmark {
+                name: name.to_string(),
+                tile_center: vec2(col, row),
+                scale,
+            });
+        }
+
+        Ok(session)
+    }
+
+    /// The function loads the session saved by the previous run of the given robot, starting a
+    /// new one if there isn't any or if it's invalid. An invalid session can't be saved.
+    pub fn load(ctx: &Context, robot_name: &str) -> Self {
+        let session = match ctx.fs.open(session_path(robot_name)) {
+            Ok(mut file) => {
+                let mut text = String::new();
+                file.read_to_string(&mut text)
+                    .map_err(|error| error.to_string())
+                    .and_then(|_| Self::parse(&text))
+                    .unwrap_or_else(|error| Self {
+                        load_error: Some(error),
+                        ..Self::default()
+                    })
+            }
+            Err(_) => Self::default(),
+        };
+
+        Self {
+            robot_name: robot_name.to_string(),
+            ..session
+        }
+    }
+
+    /// The function writes the session into the session file of its robot, unless the file
+    /// couldn't be loaded.
+    pub fn save(&self, ctx: &Context) -> GameResult {
+        if let Some(error) = &self.load_error {
+            return Err(GameError::CustomError(format!(
+                "the session file is kept as it is, since it can't be loaded ({})",
+                error
+            )));
+        }
+
+        let mut file = ctx.fs.create(session_path(&self.robot_name))?;
+        file.write_all(self.to_text().as_bytes())?;
+
+        Ok(())
+    }
+
+    /// The function returns the text of the session file.
+    fn to_text(&self) -> String {
+        let mut text = String::from(HEADER);
+
+        for bookmark in self.bookmarks.iter() {
+            text.push_str(&format!(
+                "bookmark = {}, {}, {}, {}\n",
+                bookmark.name, bookmark.tile_center.x, bookmark.tile_center.y, bookmark.scale
+            ));
+        }
+
+        text
+    }
+
+    /// The function bookmarks the given camera position and returns the bookmark.
+    /// A bookmark of the same tile is updated instead of adding another one, keeping its name.
+    pub fn add_bookmark(&mut self, tile_center: Vec2, scale: f32) -> &Bookmark {
+        let index = match self
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.tile_center.round() == tile_center.round())
+        {
+            Some(index) => {
+                self.bookmarks[index].tile_center = tile_center;
+                self.bookmarks[index].scale = scale;
+                index
+            }
+            None => {
+                self.bookmarks.push(Bookmark {
+                    name: format!("Bookmark {}", self.bookmarks.len() + 1),
+                    tile_center,
+                    scale,
+                });
+                self.bookmarks.len() - 1
+            }
+        };
+
+        // Cycling continues from the saved bookmark.
+        self.current = Some(index);
+        &self.bookmarks[index]
+    }
+
+    /// The function returns the bookmark following the last one shown or saved, going back to
+    /// the first one after the last.
+    pub fn next_bookmark(&mut self) -> Option<&Bookmark> {
+        if self.bookmarks.is_empty() {
+            return None;
+        }
+
+        let index = self
+            .current
+            .map_or(0, |current| (current + 1) % self.bookmarks.len());
+
+        self.current = Some(index);
+        self.bookmarks.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_text_round_trips() {
+        let mut session = Session::default();
+        session.add_bookmark(vec2(12.5, 30.0), 4.0);
+        session.add_bookmark(vec2(100.0, 7.25), 2.5);
+
+        let parsed = Session::parse(&session.to_text()).unwrap();
+
+        assert_eq!(parsed.bookmarks, session.bookmarks);
+        assert_eq!(parsed.bookmarks[0].name, "Bookmark 1");
+    }
+
+    #[test]
+    fn names_may_contain_commas() {
+        let session = Session::parse("bookmark = Base, north side, 3, 4, 1.5").unwrap();
+
+        assert_eq!(
+            session.bookmarks,
+            vec![Bookmark {
+                name: String::from("Base, north side"),
+                tile_center: vec2(3.0, 4.0),
+                scale: 1.5,
+            }]
+        );
+    }
+
+    #[test]
+    fn saving_the_same_tile_updates_the_bookmark() {
+        let mut session = Session::default();
+        session.add_bookmark(vec2(3.0, 4.0), 1.0);
+        session.add_bookmark(vec2(8.0, 8.0), 1.0);
+        session.add_bookmark(vec2(3.1, 3.9), 2.0);
+
+        assert_eq!(session.bookmarks.len(), 2);
+        assert_eq!(session.bookmarks[0].scale, 2.0);
+
+        // Cycling continues after the updated bookmark.
+        assert_eq!(session.next_bookmark().unwrap().name, "Bookmark 2");
+        assert_eq!(session.next_bookmark().unwrap().name, "Bookmark 1");
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        assert!(Session::parse("bookmark = Base, 3, 4").is_err());
+        assert!(Session::parse("bookmark = Base, 3, four, 1.0").is_err());
+        assert!(Session::parse("camera = Base, 3, 4, 1.0").is_err());
+    }
+
+    #[test]
+    fn non_finite_numbers_are_rejected() {
+        assert!(Session::parse("bookmark = Base, NaN, 4, 1.0").is_err());
+        assert!(Session::parse("bookmark = Base, 3, inf, 1.0").is_err());
+        assert!(Session::parse("bookmark = Base, 3, 4, -infinity").is_err());
+    }
+}
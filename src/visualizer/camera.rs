@@ -0,0 +1,474 @@
+use ggez::glam::{vec2, Vec2};
+use robotics_lib::world::tile::Tile;
+
+use super::textures::Texture;
+
+/// The modes of the camera, which decide whether it follows the robot when it moves.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum FollowMode {
+    /// The camera is centered on the robot every time it moves.
+    Always,
+    /// The camera is centered on the robot only when it leaves the window.
+    Viewport,
+    /// The camera never moves by itself.
+    Free,
+}
+
+impl FollowMode {
+    /// The function returns the mode following the current one.
+    pub(in crate::visualizer) fn next(self) -> Self {
+        match self {
+            FollowMode::Always => FollowMode::Viewport,
+            FollowMode::Viewport => FollowMode::Free,
+            FollowMode::Free => FollowMode::Always,
+        }
+    }
+
+    /// The function returns the human readable name of the mode.
+    pub(in crate::visualizer) fn name(self) -> &'static str {
+        match self {
+            FollowMode::Always => "Follow",
+            FollowMode::Viewport => "Follow at edges",
+            FollowMode::Free => "Free camera",
+        }
+    }
+}
+
+/// The orientations of the isometric view, rotated in steps of 90 degrees. The name of an
+/// orientation is the corner of the map drawn at the top of the window: the first row and the
+/// first column meet there when facing north.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum Orientation {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Orientation {
+    /// The function returns the orientation rotated clockwise by 90 degrees.
+    pub(in crate::visualizer) fn next(self) -> Self {
+        match self {
+            Orientation::North => Orientation::East,
+            Orientation::East => Orientation::South,
+            Orientation::South => Orientation::West,
+            Orientation::West => Orientation::North,
+        }
+    }
+
+    /// The function returns the human readable name of the orientation.
+    pub(in crate::visualizer) fn name(self) -> &'static str {
+        match self {
+            Orientation::North => "north",
+            Orientation::East => "east",
+            Orientation::South => "south",
+            Orientation::West => "west",
+        }
+    }
+
+    /// The function converts the (row, column) of a tile into the (row, column) of the rotated
+    /// map, as it's drawn on the window.
+    pub(in crate::visualizer) fn rotate(self, map_len: usize, row: f32, col: f32) -> (f32, f32) {
+        let last = map_len as f32 - 1.0;
+
+        match self {
+            Orientation::North => (row, col),
+            Orientation::East => (col, last - row),
+            Orientation::South => (last - row, last - col),
+            Orientation::West => (last - col, row),
+        }
+    }
+
+    /// The function converts the (row, column) of the rotated map into the (row, column) of the
+    /// tile. It's the inverse of rotate.
+    pub(in crate::visualizer) fn unrotate(self, map_len: usize, row: f32, col: f32) -> (f32, f32) {
+        let last = map_len as f32 - 1.0;
+
+        match self {
+            Orientation::North => (row, col),
+            Orientation::East => (last - col, row),
+            Orientation::South => (last - row, last - col),
+            Orientation::West => (col, last - row),
+        }
+    }
+
+    /// The function returns the index of the diagonal of the rotated map containing the given
+    /// tile. Diagonals are drawn in increasing order, from the back to the front of the view.
+    pub(in crate::visualizer) fn diagonal(self, map_len: usize, row: usize, col: usize) -> usize {
+        let (row, col) = self.rotate(map_len, row as f32, col as f32);
+
+        (row + col) as usize
+    }
+}
+
+/// The camera contains the projection of the map on the window.
+/// There are three coordinate systems:
+/// - tile coordinates, the (row, column) of a tile in the map;
+/// - world coordinates, the isometric position of the images of the tiles, without scaling;
+/// - screen coordinates, the position in pixels on the window.
+///
+/// The camera owns the origin and the scale, which convert world coordinates into screen ones.
+/// The origin is the position of the top left corner of the window in the scaled world.
+/// It also owns the orientation of the view, which every projection of tiles into world
+/// coordinates depends on.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(in crate::visualizer) struct Camera {
+    origin: Vec2,
+    scale: f32,
+    orientation: Orientation,
+}
+
+impl Camera {
+    /// The constructor creates a new camera with the given scale, the origin in the top left
+    /// corner of the world and the view facing north.
+    pub(in crate::visualizer) fn new(scale: f32) -> Self {
+        Self {
+            origin: Vec2::ZERO,
+            scale,
+            orientation: Orientation::North,
+        }
+    }
+
+    /// The function returns the origin of the camera.
+    pub(in crate::visualizer) fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// The function returns the scale of the camera.
+    pub(in crate::visualizer) fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// The function returns the orientation of the view.
+    pub(in crate::visualizer) fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// The function rotates the view clockwise by 90 degrees and returns the new orientation.
+    /// The origin is left untouched, so the caller recenters the view.
+    pub(in crate::visualizer) fn rotate(&mut self) -> Orientation {
+        self.orientation = self.orientation.next();
+        self.orientation
+    }
+
+    /// The function sets the origin of the camera.
+    pub(in crate::visualizer) fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+
+    /// The function moves the camera by the given offset, in screen coordinates.
+    pub(in crate::visualizer) fn pan(&mut self, offset: Vec2) {
+        self.origin += offset;
+    }
+
+    /// The function sets the scale of the camera, keeping the world point under the given point
+    /// of the window fixed.
+    pub(in crate::visualizer) fn zoom_at(&mut self, scale: f32, focus: Vec2) {
+        let world = self.screen_to_world(focus);
+
+        self.scale = scale;
+        self.origin = world * scale - focus;
+    }
+
+    /// The function returns the origin which shows the given world point in the center of a
+    /// window of the given size.
+    pub(in crate::visualizer) fn centered_on(&self, world: Vec2, window_size: Vec2) -> Vec2 {
+        world * self.scale - window_size * 0.5
+    }
+
+    /// The function converts world coordinates into screen coordinates.
+    pub(in crate::visualizer) fn world_to_screen(&self, world: Vec2) -> Vec2 {
+        world * self.scale - self.origin
+    }
+
+    /// The function converts screen coordinates into world coordinates.
+    pub(in crate::visualizer) fn screen_to_world(&self, screen: Vec2) -> Vec2 {
+        (screen + self.origin) / self.scale
+    }
+
+    /// The function returns the position of the top left corner of the image of the tile in the
+    /// given row and column, seen from the given orientation, in world coordinates. Fractional
+    /// coordinates are between tiles.
+    pub(in crate::visualizer) fn tile_to_world(
+        map_len: usize,
+        orientation: Orientation,
+        row: f32,
+        col: f32,
+    ) -> Vec2 {
+        let (row, col) = orientation.rotate(map_len, row, col);
+
+        vec2(
+            (Texture::width() * 0.5) * (map_len as f32 - row + col - 1.0),
+            ((Texture::height() - 1.0) * 0.25) * (row + col),
+        )
+    }
+
+    /// The function converts the world position of the top left corner of the image of a tile
+    /// into the (row, column) of the tile, seen from the given orientation. It's the inverse of
+    /// tile_to_world.
+    pub(in crate::visualizer) fn world_to_tile(
+        map_len: usize,
+        orientation: Orientation,
+        world: Vec2,
+    ) -> (f32, f32) {
+        let u = world.x / (Texture::width() * 0.5) - (map_len as f32 - 1.0); // column - row
+        let v = world.y / ((Texture::height() - 1.0) * 0.25); // column + row
+
+        orientation.unrotate(map_len, (v - u) * 0.5, (u + v) * 0.5)
+    }
+
+    /// The function returns the vertical distance between the top of the image of a tile and its
+    /// top face. Half tiles have their top face lower than block tiles.
+    pub(in crate::visualizer) fn top_face_offset(tile: &Tile) -> f32 {
+        if tile.elevation < 3 {
+            4.0
+        } else {
+            0.0
+        }
+    }
+
+    /// The function returns the position of the top left corner of the image of the given tile,
+    /// moved down to its top face, in world coordinates. Overlays following the top face of
+    /// the tiles are drawn there.
+    pub(in crate::visualizer) fn top_face(
+        map_len: usize,
+        orientation: Orientation,
+        row: usize,
+        col: usize,
+        tile: &Tile,
+    ) -> Vec2 {
+        Self::tile_to_world(map_len, orientation, row as f32, col as f32)
+            + vec2(0.0, Self::top_face_offset(tile))
+    }
+
+    /// The function returns the center of the top face of the given tile, in world coordinates.
+    pub(in crate::visualizer) fn top_face_center(
+        map_len: usize,
+        orientation: Orientation,
+        row: usize,
+        col: usize,
+        tile: &Tile,
+    ) -> Vec2 {
+        Self::top_face(map_len, orientation, row, col, tile) + Self::face_center()
+    }
+
+    /// The function returns the center of the top face of a block, relative to the top left
+    /// corner of its image.
+    pub(in crate::visualizer) fn face_center() -> Vec2 {
+        // The top face of a block starts on the second row of pixels of the image.
+        vec2(
+            Texture::width() * 0.5,
+            1.0 + (Texture::height() - 1.0) * 0.25,
+        )
+    }
+
+    /// The function returns the coordinates (row, column) of the tile whose top face contains the
+    /// given point, expressed in world coordinates, seen from the given orientation.
+    /// It inverts the projection trying both the block and the half top face, keeping the
+    /// front-most tile whose height matches the tried face.
+    pub(in crate::visualizer) fn tile_at(
+        map: &[Vec<Tile>],
+        orientation: Orientation,
+        world: Vec2,
+    ) -> Option<(usize, usize)> {
+        let map_len = map.len();
+
+        let candidates = [0.0, 4.0]
+            .iter()
+            .filter_map(|offset| {
+                let (row, col) = Self::world_to_tile(
+                    map_len,
+                    orientation,
+                    world - Self::face_center() - vec2(0.0, *offset),
+                );
+                let (row, col) = (row.round(), col.round());
+
+                if row < 0.0 || col < 0.0 || row >= map_len as f32 || col >= map_len as f32 {
+                    None
+                } else {
+                    Some((*offset, (row as usize, col as usize)))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        candidates
+            .iter()
+            .filter(|(offset, (row, col))| Self::top_face_offset(&map[*row][*col]) == *offset)
+            .max_by_key(|(_, (row, col))| orientation.diagonal(map_len, *row, *col))
+            .or(candidates.first())
+            .map(|(_, coords)| *coords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use super::*;
+
+    fn tile(elevation: usize) -> Tile {
+        Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation,
+        }
+    }
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) < 1e-4,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn tile_to_world_places_the_corners_of_the_map() {
+        // The first row goes down to the left, the first column goes down to the right.
+        assert_near(
+            Camera::tile_to_world(4, Orientation::North, 0.0, 0.0),
+            vec2(24.0, 0.0),
+        );
+        assert_near(
+            Camera::tile_to_world(4, Orientation::North, 3.0, 0.0),
+            vec2(0.0, 11.25),
+        );
+        assert_near(
+            Camera::tile_to_world(4, Orientation::North, 0.0, 3.0),
+            vec2(48.0, 11.25),
+        );
+        assert_near(
+            Camera::tile_to_world(4, Orientation::North, 3.0, 3.0),
+            vec2(24.0, 22.5),
+        );
+    }
+
+    #[test]
+    fn world_to_tile_inverts_tile_to_world() {
+        for (row, col) in [(0.0, 0.0), (5.0, 2.0), (2.5, 7.25), (63.0, 63.0)] {
+            let world = Camera::tile_to_world(64, Orientation::North, row, col);
+            let (r, c) = Camera::world_to_tile(64, Orientation::North, world);
+
+            assert_near(vec2(c, r), vec2(col, row));
+        }
+    }
+
+    #[test]
+    fn screen_to_world_inverts_world_to_screen() {
+        let mut camera = Camera::new(2.5);
+        camera.set_origin(vec2(-120.0, 340.0));
+
+        let world = vec2(17.0, -3.5);
+        let screen = camera.world_to_screen(world);
+
+        assert_near(screen, vec2(162.5, -348.75));
+        assert_near(camera.screen_to_world(screen), world);
+    }
+
+    #[test]
+    fn zoom_at_keeps_the_focus_fixed() {
+        let mut camera = Camera::new(1.0);
+        camera.set_origin(vec2(50.0, 80.0));
+
+        let focus = vec2(300.0, 200.0);
+        let world = camera.screen_to_world(focus);
+
+        camera.zoom_at(3.0, focus);
+
+        assert_eq!(camera.scale(), 3.0);
+        assert_near(camera.world_to_screen(world), focus);
+    }
+
+    #[test]
+    fn centered_on_shows_the_point_in_the_center() {
+        let mut camera = Camera::new(2.0);
+        let world = Camera::tile_to_world(16, Orientation::North, 4.0, 9.0);
+
+        camera.set_origin(camera.centered_on(world, vec2(800.0, 600.0)));
+
+        assert_near(camera.world_to_screen(world), vec2(400.0, 300.0));
+    }
+
+    #[test]
+    fn tile_at_picks_the_tile_under_its_top_face() {
+        let mut map = vec![vec![tile(1); 8]; 8];
+        map[3][4] = tile(5);
+
+        // Both blocks and half tiles are picked from the center of their top face.
+        for (row, col) in [(0, 0), (2, 6), (3, 4), (7, 7)] {
+            let center = Camera::top_face_center(8, Orientation::North, row, col, &map[row][col]);
+
+            assert_eq!(
+                Camera::tile_at(&map, Orientation::North, center),
+                Some((row, col))
+            );
+        }
+    }
+
+    #[test]
+    fn tile_at_ignores_points_outside_of_the_map() {
+        let map = vec![vec![tile(5); 8]; 8];
+
+        assert_eq!(
+            Camera::tile_at(&map, Orientation::North, vec2(-100.0, -100.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn rotations_cycle_through_the_four_orientations() {
+        let mut camera = Camera::new(1.0);
+
+        assert_eq!(camera.rotate(), Orientation::East);
+        assert_eq!(camera.rotate(), Orientation::South);
+        assert_eq!(camera.rotate(), Orientation::West);
+        assert_eq!(camera.rotate(), Orientation::North);
+    }
+
+    #[test]
+    fn rotations_move_the_corners_of_the_map() {
+        // The corner at the top of the window is the first tile facing north. Every rotation
+        // turns the map clockwise, bringing the corner on the left of the window to the top.
+        let top = vec2(24.0, 0.0);
+
+        assert_near(Camera::tile_to_world(4, Orientation::North, 0.0, 0.0), top);
+        assert_near(Camera::tile_to_world(4, Orientation::East, 3.0, 0.0), top);
+        assert_near(Camera::tile_to_world(4, Orientation::South, 3.0, 3.0), top);
+        assert_near(Camera::tile_to_world(4, Orientation::West, 0.0, 3.0), top);
+    }
+
+    #[test]
+    fn world_to_tile_inverts_tile_to_world_in_every_orientation() {
+        let mut orientation = Orientation::North;
+
+        for _ in 0..4 {
+            for (row, col) in [(0.0, 0.0), (5.0, 2.0), (2.5, 7.25), (63.0, 0.0)] {
+                let world = Camera::tile_to_world(64, orientation, row, col);
+                let (r, c) = Camera::world_to_tile(64, orientation, world);
+
+                assert_near(vec2(c, r), vec2(col, row));
+            }
+
+            orientation = orientation.next();
+        }
+    }
+
+    #[test]
+    fn tile_at_picks_the_front_tile_in_every_orientation() {
+        let mut map = vec![vec![tile(1); 8]; 8];
+        map[3][4] = tile(5);
+
+        let mut orientation = Orientation::North;
+
+        for _ in 0..4 {
+            for (row, col) in [(0, 0), (2, 6), (3, 4), (7, 7), (7, 0)] {
+                let center = Camera::top_face_center(8, orientation, row, col, &map[row][col]);
+
+                assert_eq!(Camera::tile_at(&map, orientation, center), Some((row, col)));
+            }
+
+            orientation = orientation.next();
+        }
+    }
+}
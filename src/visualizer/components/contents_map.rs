@@ -1,31 +1,48 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use ggez::{
     context::Has,
-    glam::vec2,
-    graphics::{Color, DrawParam, GraphicsContext, InstanceArray},
+    glam::{vec2, Vec2},
+    graphics::{Color, DrawParam, GraphicsContext, Image, InstanceArray},
 };
 use robotics_lib::world::tile::Tile;
 
-use crate::visualizer::textures::Texture;
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    fog::Fog,
+    textures::Texture,
+};
 
-use super::{Component, CoordinatedInstance};
+use super::{filter::dim, Component, CoordinatedInstance};
 
 /// The struct contains the state of the content map component.
 pub(in crate::visualizer) struct ContentsMapComponent {
     map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
-    instances: HashMap<Texture, CoordinatedInstance>,
+    fog_rc: Rc<RefCell<Fog>>,
+    images: HashMap<Texture, Image>,
+    instances: Vec<HashMap<Texture, CoordinatedInstance>>,
+    filter: HashSet<Texture>,
+    highlight: InstanceArray,
+    hidden: HashSet<Texture>,
+    orientation: Orientation,
 }
 
 /// The struct contains the parameters for drawing the component.
 pub(in crate::visualizer) struct ContentsMapComponentParam {
-    scale: f32,
+    camera: Camera,
+    window_size: Vec2,
+    tint: Color,
+    time: f32,
 }
 
 /// The union contains the types of updates for the component.
 pub(in crate::visualizer) enum ContentsMapComponentUpdateType {
-    WorldVisibility(Vec<Vec<Option<Tile>>>),
-    ContentChange(Tile, (usize, usize)),
+    Filter(HashSet<Texture>),
+    Visibility(HashSet<Texture>),
 }
 
 /// The struct contains the parameters for updating the component.
@@ -33,105 +50,222 @@ pub(in crate::visualizer) struct ContentsMapComponentUpdateParam {
     _type: ContentsMapComponentUpdateType,
 }
 
+/// The contents are kept in diagonals, like the tiles of TilesMapComponent, so they're drawn from
+/// the back to the front of the view even when they change. Contents are sparse, so a diagonal
+/// has the instance of a texture only once a content of that texture is placed on it.
 impl ContentsMapComponent {
     /// The constructor creates a new instance of the component from the shared reference to the
-    /// map.
-    pub fn from_map(gfx: &impl Has<GraphicsContext>, map_rc: Rc<RefCell<Vec<Vec<Tile>>>>) -> Self {
-        // Fills the following hashmap with the instances of the textures for every tyle type.
-        let mut instances = HashMap::new();
-
-        map_rc
-            .clone()
-            .borrow()
-            .iter()
-            .enumerate()
-            .for_each(|(y, row)| {
-                row.iter().enumerate().for_each(|(x, tile)| {
-                    if let Some(texture) = Texture::from_content(&tile.content) {
-                        let image_x = (Texture::width() * 0.5) * (row.len() - y + x - 1) as f32;
-                        let image_y = ((Texture::height() - 1.0) * 0.25) * (x + y) as f32;
-                        let offset_y = if tile.elevation < 3 { 2.0 } else { 6.0 };
-
-                        let instance =
-                            instances
-                                .entry(texture)
-                                .or_insert_with(|| CoordinatedInstance {
-                                    array: InstanceArray::new(gfx, texture.get_image(gfx)),
-                                    elements: Vec::new(),
-                                });
-
-                        // Pushes the draw param in the instance of the texture and adds the
-                        // coordinates to the elements vector.
-                        instance.array.push(
-                            DrawParam::new()
-                                .dest(vec2(image_x, image_y - offset_y))
-                                .color(Color::from_rgba(0, 0, 0, 127)),
-                        );
-                        instance.elements.push((x, y));
-                    }
-                });
-            });
-
-        Self { instances, map_rc }
+    /// map, colored by the given fog of war. The view faces north at first.
+    pub fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog_rc: Rc<RefCell<Fog>>,
+    ) -> Self {
+        // The images are loaded once and shared by the instances of every diagonal.
+        let images = Texture::get_contents()
+            .into_iter()
+            .map(|texture| (texture, texture.get_image(gfx)))
+            .collect::<HashMap<_, _>>();
+
+        let instances = Self::create_instances(
+            gfx,
+            &images,
+            &map_rc.borrow(),
+            &fog_rc.borrow(),
+            Orientation::North,
+        );
+
+        Self {
+            images,
+            instances,
+            map_rc,
+            fog_rc,
+            filter: HashSet::new(),
+            highlight: InstanceArray::new(gfx, Texture::Highlight.get_image(gfx)),
+            hidden: HashSet::new(),
+            orientation: Orientation::North,
+        }
+    }
+
+    /// This private method creates the instances of the textures of every diagonal of the map,
+    /// from the back to the front of the view, filled with the contents of the map.
+    fn create_instances(
+        gfx: &impl Has<GraphicsContext>,
+        images: &HashMap<Texture, Image>,
+        map: &[Vec<Tile>],
+        fog: &Fog,
+        orientation: Orientation,
+    ) -> Vec<HashMap<Texture, CoordinatedInstance>> {
+        let map_len = map.len();
+
+        let mut instances = (0..(2 * map_len).saturating_sub(1))
+            .map(|_| HashMap::new())
+            .collect::<Vec<_>>();
+
+        for (y, row) in map.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if let Some(texture) = Texture::from_content(&tile.content) {
+                    let position = Self::position(map_len, orientation, y, x, tile);
+
+                    // Pushes the draw param in the instance of the texture of its diagonal,
+                    // traced by the coordinates of the tile.
+                    Self::instance(
+                        gfx,
+                        images,
+                        &mut instances[orientation.diagonal(map_len, y, x)],
+                        texture,
+                    )
+                    .push(
+                        (x, y),
+                        DrawParam::new().dest(position).color(fog.color(y, x)),
+                    );
+                }
+            }
+        }
+
+        instances
+    }
+
+    /// The function returns the instance of the given texture in the given diagonal, creating it
+    /// when the first content of the texture is placed on the diagonal.
+    fn instance<'a>(
+        gfx: &impl Has<GraphicsContext>,
+        images: &HashMap<Texture, Image>,
+        diagonal_instances: &'a mut HashMap<Texture, CoordinatedInstance>,
+        texture: Texture,
+    ) -> &'a mut CoordinatedInstance {
+        diagonal_instances
+            .entry(texture)
+            .or_insert_with(|| CoordinatedInstance::new(gfx, images[&texture].clone()))
+    }
+
+    /// The function returns the position of the image of the content of the given tile, in world
+    /// coordinates. Contents stand on the top face of their tile.
+    fn position(
+        map_len: usize,
+        orientation: Orientation,
+        row: usize,
+        col: usize,
+        tile: &Tile,
+    ) -> Vec2 {
+        Camera::top_face(map_len, orientation, row, col, tile) - vec2(0.0, 6.0)
+    }
+
+    /// The function rebuilds the diagonals of the contents for the given orientation of the view.
+    pub(in crate::visualizer) fn set_orientation(
+        &mut self,
+        gfx: &impl Has<GraphicsContext>,
+        orientation: Orientation,
+    ) {
+        self.orientation = orientation;
+        self.instances = Self::create_instances(
+            gfx,
+            &self.images,
+            &self.map_rc.borrow(),
+            &self.fog_rc.borrow(),
+            orientation,
+        );
+
+        if !self.filter.is_empty() {
+            self.update_highlight();
+        }
+    }
+
+    /// The function colors every content by the current state of the fog of war.
+    pub(in crate::visualizer) fn refresh_fog(&mut self) {
+        let fog = self.fog_rc.borrow();
+
+        for diagonal_instances in self.instances.iter_mut() {
+            for instance in diagonal_instances.values_mut() {
+                instance.update_all(|(x, y), draw_param| draw_param.color(fog.color(y, x)));
+            }
+        }
+    }
+
+    /// The function colors the contents in the given coordinates (row, column) by the current
+    /// state of the fog of war.
+    pub(in crate::visualizer) fn refresh_fog_at(&mut self, coords: &[(usize, usize)]) {
+        let fog = self.fog_rc.borrow();
+        let map_len = self.map_rc.borrow().len();
+
+        // The content is looked for among the instances of its diagonal, as the map may already
+        // hold a newer content than the drawn one.
+        for (y, x) in coords.iter().copied() {
+            let diagonal = self.orientation.diagonal(map_len, y, x);
+
+            for instance in self.instances[diagonal].values_mut() {
+                instance.update((x, y), |draw_param| draw_param.color(fog.color(y, x)));
+            }
+        }
+    }
+
+    /// The function places the highlight below every content matching the filter.
+    fn update_highlight(&mut self) {
+        let map = self.map_rc.borrow();
+        let map_len = map.len();
+
+        self.highlight.set(
+            self.instances
+                .iter()
+                .flat_map(|diagonal_instances| {
+                    self.filter
+                        .iter()
+                        .filter_map(|texture| diagonal_instances.get(texture))
+                })
+                .flat_map(|instance| instance.elements().iter())
+                .map(|(x, y)| {
+                    DrawParam::new().dest(Camera::top_face(
+                        map_len,
+                        self.orientation,
+                        *y,
+                        *x,
+                        &map[*y][*x],
+                    ))
+                }),
+        );
     }
 
-    /// The function handles event of content change. Is called internally by the update method.
-    fn update_content(&mut self, tile: &Tile, coords: (usize, usize)) {
+    /// The function handles event of content change of the tile in the given coordinates (row,
+    /// column).
+    pub(in crate::visualizer) fn set_content(
+        &mut self,
+        gfx: &impl Has<GraphicsContext>,
+        tile: &Tile,
+        coords: (usize, usize),
+    ) {
         // Borrow the mutable reference to the map
         let mut map = self.map_rc.borrow_mut();
+        let map_len = map.len();
         let y = coords.0; // row
         let x = coords.1; // column
 
-        let previous_texture = Texture::from_content(&map[y][x].content);
-
-        let current_texture = Texture::from_content(&tile.content);
-
-        if let (Some(previous_texture), Some(current_texture)) = (previous_texture, current_texture)
-        {
-            if let Some(instance) = self.instances.get_mut(&previous_texture) {
-                // Gets the position of the content in the instance array.
-                let element_position: usize = instance
-                    .elements
-                    .iter()
-                    .position(|(e_x, e_y)| x == *e_x && y == *e_y)
-                    .unwrap()
-                    .clone();
-
-                // Gets corresponding draw param.
-                let draw_param = instance
-                    .array
-                    .instances()
-                    .get(element_position)
-                    .unwrap()
-                    .clone();
-
-                // Removes the draw param slicing the array.
-                instance.array.set(
-                    instance
-                        .array
-                        .instances()
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(i, draw_param)| {
-                            if i != element_position {
-                                Some(draw_param.clone())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                );
-
-                // Pushes the draw param in the new instance of the texture.
-                self.instances
-                    .get_mut(&current_texture)
-                    .unwrap()
-                    .array
-                    .push(draw_param.clone());
-            }
+        // Contents change in place, so the content stays in its diagonal.
+        let diagonal_instances = &mut self.instances[self.orientation.diagonal(map_len, y, x)];
+
+        // Removes the draw param of the drawn content, whatever its texture.
+        diagonal_instances
+            .values_mut()
+            .find_map(|instance| instance.swap_remove((x, y)));
+
+        // Pushes the draw param in the instance of the new texture, if the tile still has a
+        // content.
+        if let Some(texture) = Texture::from_content(&tile.content) {
+            let position = Self::position(map_len, self.orientation, y, x, &map[y][x]);
 
-            // Edits the content of the map.
-            map[y][x].content = tile.content.clone();
+            Self::instance(gfx, &self.images, diagonal_instances, texture).push(
+                (x, y),
+                DrawParam::new()
+                    .dest(position)
+                    .color(self.fog_rc.borrow().color(y, x)),
+            );
+        }
+
+        // Edits the content of the map.
+        map[y][x].content = tile.content.clone();
+        drop(map);
+
+        if !self.filter.is_empty() {
+            self.update_highlight();
         }
     }
 }
@@ -145,14 +279,61 @@ impl Component<ContentsMapComponentParam, ContentsMapComponentUpdateParam>
         _draw_param: DrawParam,
         component_param: ContentsMapComponentParam,
     ) -> Result<(), ggez::GameError> {
-        // Draws the instances of the textures.
-        for (_texture, instance) in &self.instances {
+        let scale = vec2(
+            component_param.camera.scale(),
+            component_param.camera.scale(),
+        );
+
+        // Draws the pulsing highlight of the contents matching the filter.
+        if !self.filter.is_empty() {
+            let pulse = 0.5 + 0.3 * (component_param.time * 4.0).sin();
+
             canvas.draw(
-                &instance.array,
-                DrawParam::new().scale(vec2(component_param.scale, component_param.scale)),
+                &self.highlight,
+                DrawParam::new()
+                    .scale(scale)
+                    .color(Color::new(1.0, 0.84, 0.0, pulse)),
             );
         }
 
+        let camera = component_param.camera;
+        let map_len = self.map_rc.borrow().len();
+
+        // Draws the instances of the textures in the correct order, from the back to the front
+        // of the view. When a filter is set, the contents not matching it are dimmed.
+        for (y, diagonal_instances) in self.instances.iter().enumerate() {
+            // Skips the diagonals out of the window, like the tiles map does. Contents stand
+            // above their tile, so they're given a tile of margin.
+            let row_position = camera
+                .world_to_screen(Camera::tile_to_world(
+                    map_len,
+                    Orientation::North,
+                    y as f32,
+                    0.0,
+                ))
+                .y;
+            if row_position + Texture::height() * camera.scale() < 0.0
+                || row_position - Texture::height() * camera.scale()
+                    >= component_param.window_size.y
+            {
+                continue;
+            }
+
+            for (texture, instance) in diagonal_instances {
+                if instance.is_empty() || self.hidden.contains(texture) {
+                    continue;
+                }
+
+                let tint = if self.filter.is_empty() || self.filter.contains(texture) {
+                    component_param.tint
+                } else {
+                    dim(component_param.tint)
+                };
+
+                canvas.draw(instance.array(), DrawParam::new().scale(scale).color(tint));
+            }
+        }
+
         Ok(())
     }
 
@@ -161,45 +342,12 @@ impl Component<ContentsMapComponentParam, ContentsMapComponentUpdateParam>
         update_param: ContentsMapComponentUpdateParam,
     ) -> Result<(), ggez::GameError> {
         match update_param._type {
-            ContentsMapComponentUpdateType::WorldVisibility(current_world) => {
-                // For every tile in the world, updates the corresponding draw param in the instance
-                for (y, row) in current_world.iter().enumerate() {
-                    for (x, tile) in row.iter().enumerate() {
-                        // If the tile is not None, robots knows it and it's visible.
-                        // So, it updates the corrisponding the draw param.
-                        if let Some(tile) = tile {
-                            if let Some(texture) = Texture::from_content(&tile.content) {
-                                if let Some(instance) = self.instances.get_mut(&texture) {
-                                    // Gets the position of the considered content in the instance array.
-                                    let element_position: usize = instance
-                                        .elements
-                                        .iter()
-                                        .position(|(e_x, e_y)| x == *e_x && y == *e_y)
-                                        .unwrap()
-                                        .clone();
-
-                                    // Gets the corresponding draw param from the instance array.
-                                    let draw_param = instance
-                                        .array
-                                        .instances()
-                                        .get(element_position)
-                                        .unwrap()
-                                        .clone();
-
-                                    // Updates the draw param in the instance array with the
-                                    // white color, which clears the previous grey effect.
-                                    instance.array.update(
-                                        element_position as u32,
-                                        draw_param.color(Color::WHITE),
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
+            ContentsMapComponentUpdateType::Filter(filter) => {
+                self.filter = filter;
+                self.update_highlight();
             }
-            ContentsMapComponentUpdateType::ContentChange(tile, coords) => {
-                self.update_content(&tile, coords);
+            ContentsMapComponentUpdateType::Visibility(hidden) => {
+                self.hidden = hidden;
             }
         }
 
@@ -209,8 +357,18 @@ impl Component<ContentsMapComponentParam, ContentsMapComponentUpdateParam>
 
 impl ContentsMapComponentParam {
     /// The constructor creates a new instance of the parameters for the component.
-    pub(in crate::visualizer) fn new(scale: f32) -> Self {
-        Self { scale }
+    pub(in crate::visualizer) fn new(
+        camera: Camera,
+        window_size: Vec2,
+        tint: Color,
+        time: f32,
+    ) -> Self {
+        Self {
+            camera,
+            window_size,
+            tint,
+            time,
+        }
     }
 }
 
@@ -7,17 +7,30 @@ use ggez::{
     },
 };
 
+use crate::visualizer::layout::{Layout, Panel};
+
 use super::Component;
 
+/// Preferred width of the dialog box, narrowed on small windows.
+const DIALOG_WIDTH: f32 = 900.0;
+
+/// Height of the dialog box.
+const DIALOG_HEIGHT: f32 = 100.0;
+
+/// Space between the border of the dialog box and its text.
+const PADDING: f32 = 20.0;
+
 /// The dialog component implements Component and displays a dialog box with a text.
+/// The box is anchored to the top left corner of the window and fits its width.
 pub(in crate::visualizer) struct DialogComponent {
     mesh: Mesh,
     text: Text,
+    width: f32,
 }
 
-/// The struct contains the origin of the dialog component.
+/// The struct contains the layout of the window the dialog component is drawn in.
 pub(in crate::visualizer) struct DialogComponentParam {
-    origin: Vec2,
+    layout: Layout,
 }
 
 /// The struct contains the text to be updated in the dialog component.
@@ -26,41 +39,76 @@ pub(in crate::visualizer) struct DialogComponentUpdateParam {
 }
 
 impl DialogComponent {
-    /// The constructor creates a new instance of the dialog component.
-    pub(crate) fn new(gfx: &impl Has<GraphicsContext>, text: String) -> Self {
+    /// The constructor creates a new instance of the dialog component, fitting the given
+    /// layout of the window.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>, text: String, layout: Layout) -> Self {
+        let width = layout.fit_width(DIALOG_WIDTH);
+
+        // The font has been loaded previously in the main file
+        let mut text = Text::new(
+            TextFragment::new(text)
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(40.0),
+        );
+        text.set_wrap(true);
+
+        let mut dialog = Self {
+            mesh: Self::build_mesh(gfx, width),
+            text,
+            width,
+        };
+
+        dialog.resize(gfx, layout);
+        dialog
+    }
+
+    /// The function fits the dialog box to the given layout of the window, after it has been
+    /// resized.
+    pub(crate) fn resize(&mut self, gfx: &impl Has<GraphicsContext>, layout: Layout) {
+        let width = layout.fit_width(DIALOG_WIDTH);
+
+        if width != self.width {
+            self.mesh = Self::build_mesh(gfx, width);
+            self.width = width;
+        }
+
+        // Sets the text bounds inside the padding of the box
+        self.text.set_bounds(vec2(
+            (width - PADDING * 2.0).max(0.0),
+            DIALOG_HEIGHT - PADDING * 2.0,
+        ));
+    }
+
+    /// The function returns the size of the dialog box.
+    pub(crate) fn size(&self) -> Vec2 {
+        vec2(self.width, DIALOG_HEIGHT)
+    }
+
+    /// The function builds the background mesh of a dialog box of the given width.
+    fn build_mesh(gfx: &impl Has<GraphicsContext>, width: f32) -> Mesh {
+        // An empty rectangle can't be tessellated.
+        let width = width.max(1.0);
+
         // Builds background mesh
         let mut mesh_builder = MeshBuilder::new();
         mesh_builder
             .rectangle(
                 DrawMode::Fill(FillOptions::default()),
-                Rect::new(0.0, 0.0, 900.0, 100.0),
+                Rect::new(0.0, 0.0, width, DIALOG_HEIGHT),
                 Color::from_rgba_u32(0x000080AA), // It has a bit of transparency
             )
             .unwrap();
         mesh_builder
             .rectangle(
                 DrawMode::Stroke(StrokeOptions::default().with_line_width(5.0)),
-                Rect::new(0.0, 0.0, 900.0, 100.0),
+                Rect::new(0.0, 0.0, width, DIALOG_HEIGHT),
                 Color::from_rgba_u32(0x000051FF),
             )
             .unwrap();
 
         // Creates the mesh
-        let mesh = Mesh::from_data(gfx, mesh_builder.build());
-
-        // The font has been loaded previously in the main file
-        let mut text = Text::new(
-            TextFragment::new(text)
-                .font("kode")
-                .color(Color::WHITE)
-                .scale(40.0),
-        );
-
-        // Sets the text bounds and wrap
-        text.set_bounds(vec2(860.0, 60.0));
-        text.set_wrap(true);
-
-        Self { mesh, text }
+        Mesh::from_data(gfx, mesh_builder.build())
     }
 }
 
@@ -71,20 +119,17 @@ impl Component<DialogComponentParam, DialogComponentUpdateParam> for DialogCompo
         draw_param: ggez::graphics::DrawParam,
         component_param: DialogComponentParam,
     ) -> Result<(), ggez::GameError> {
+        let Some(position) = component_param.layout.position(Panel::Dialog) else {
+            return Ok(());
+        };
+
         // Draws background mesh
-        canvas.draw(
-            &self.mesh,
-            draw_param
-                .clone()
-                .dest(component_param.origin + vec2(10.0, 10.0)),
-        );
+        canvas.draw(&self.mesh, draw_param.dest(position));
 
         // Draws dialog text
         canvas.draw(
             &self.text,
-            draw_param
-                .clone()
-                .dest(component_param.origin + vec2(30.0, 20.0)),
+            draw_param.dest(position + vec2(PADDING, PADDING / 2.0)),
         );
 
         Ok(())
@@ -100,8 +145,8 @@ impl Component<DialogComponentParam, DialogComponentUpdateParam> for DialogCompo
 
 impl DialogComponentParam {
     /// The constructor creates a new instance of the dialog component parameter.
-    pub(crate) fn new(origin: Vec2) -> Self {
-        Self { origin }
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self { layout }
     }
 }
 
@@ -0,0 +1,241 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{
+        Canvas, Color, DrawParam, GraphicsContext, InstanceArray, Quad, Rect, Text, TextFragment,
+    },
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    fog::Fog,
+    layout::{Layout, Panel},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// Colors of the elevation scale, from the lowest to the highest tile.
+const COLOR_STOPS: [u32; 4] = [0x1A9850, 0xFEE08B, 0x8C510A, 0xFFFFFF];
+
+/// Width of the legend box.
+const LEGEND_WIDTH: f32 = 320.0;
+
+/// Height of the legend box.
+const LEGEND_HEIGHT: f32 = 90.0;
+
+/// The elevation component implements Component and shades every tile known by the robot by its
+/// actual elevation, with a legend mapping the colors to elevation values. The legend is drawn
+/// on the screen-space pass, apart from the shaded tiles.
+/// The shading is rebuilt only when the view is enabled or rotated, and when the robot discovers
+/// new tiles.
+pub(in crate::visualizer) struct ElevationComponent {
+    map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    fog_rc: Rc<RefCell<Fog>>,
+    array: InstanceArray,
+    range: (usize, usize),
+    orientation: Orientation,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct ElevationComponentParam {
+    camera: Camera,
+}
+
+impl ElevationComponent {
+    /// The constructor creates a new instance of the elevation component from the shared
+    /// references to the map and to the fog of war, which tells the tiles known by the robot.
+    /// The component is disabled by default.
+    pub(crate) fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog_rc: Rc<RefCell<Fog>>,
+    ) -> Self {
+        Self {
+            map_rc,
+            fog_rc,
+            array: InstanceArray::new(gfx, Texture::Highlight.get_image(gfx)),
+            range: (0, 0),
+            orientation: Orientation::North,
+            enabled: false,
+        }
+    }
+
+    /// The function moves the shaded tiles to their position for the given orientation of the
+    /// view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+
+        if self.enabled {
+            self.rebuild();
+        }
+    }
+
+    /// The function enables or disables the elevation view and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+
+        if self.enabled {
+            self.rebuild();
+        } else {
+            self.array.clear();
+        }
+
+        self.enabled
+    }
+
+    /// The function returns the size of the legend of the elevation scale, if the elevation
+    /// view is enabled.
+    pub(crate) fn legend_size(&self) -> Option<Vec2> {
+        self.enabled.then(|| vec2(LEGEND_WIDTH, LEGEND_HEIGHT))
+    }
+
+    /// The function draws the legend of the elevation scale where the given layout has placed
+    /// it, if the elevation view is enabled.
+    pub(crate) fn draw_legend(&self, canvas: &mut Canvas, draw_param: DrawParam, layout: &Layout) {
+        let Some(position) = layout.position(Panel::Elevation) else {
+            return;
+        };
+
+        canvas.draw(
+            &Quad,
+            draw_param
+                .dest_rect(Rect::new(
+                    position.x,
+                    position.y,
+                    LEGEND_WIDTH,
+                    LEGEND_HEIGHT,
+                ))
+                .color(Color::from_rgba_u32(0x000080AA)),
+        );
+
+        let title = Text::new(
+            TextFragment::new("Elevation")
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(24.0),
+        );
+        canvas.draw(&title, draw_param.dest(position + vec2(10.0, 5.0)));
+
+        // The color scale is drawn as a sequence of thin bars.
+        let bars = 64;
+        let bar_width = (LEGEND_WIDTH - 20.0) / bars as f32;
+        for i in 0..bars {
+            canvas.draw(
+                &Quad,
+                draw_param
+                    .dest_rect(Rect::new(
+                        position.x + 10.0 + i as f32 * bar_width,
+                        position.y + 35.0,
+                        bar_width + 0.5,
+                        20.0,
+                    ))
+                    .color(elevation_color(i as f32 / (bars - 1) as f32)),
+            );
+        }
+
+        // Writes the lowest and the highest elevation below the scale.
+        for (value, x) in [(self.range.0, 10.0), (self.range.1, LEGEND_WIDTH - 50.0)] {
+            let label = Text::new(
+                TextFragment::new(value.to_string())
+                    .font("kode")
+                    .color(Color::WHITE)
+                    .scale(20.0),
+            );
+            canvas.draw(&label, draw_param.dest(position + vec2(x, 60.0)));
+        }
+    }
+
+    /// The function shades every tile known by the robot by its elevation, normalized between
+    /// the lowest and the highest known tile.
+    fn rebuild(&mut self) {
+        let map = self.map_rc.borrow();
+        let fog = self.fog_rc.borrow();
+        let map_len = map.len();
+
+        let known = map
+            .iter()
+            .enumerate()
+            .flat_map(|(row, tiles)| {
+                tiles
+                    .iter()
+                    .enumerate()
+                    .map(move |(col, tile)| (row, col, tile))
+            })
+            .filter(|(row, col, _)| fog.is_known(*row, *col))
+            .collect::<Vec<_>>();
+
+        let lowest = known.iter().map(|(_, _, tile)| tile.elevation).min();
+        let highest = known.iter().map(|(_, _, tile)| tile.elevation).max();
+        self.range = (lowest.unwrap_or(0), highest.unwrap_or(0));
+
+        let span = (self.range.1 - self.range.0).max(1) as f32;
+        let lowest = self.range.0;
+        let orientation = self.orientation;
+
+        self.array.set(known.iter().map(|(row, col, tile)| {
+            let height = (tile.elevation - lowest) as f32 / span;
+
+            DrawParam::new()
+                .dest(Camera::top_face(map_len, orientation, *row, *col, tile))
+                .color(elevation_color(height))
+        }));
+    }
+}
+
+impl Component<ElevationComponentParam, ()> for ElevationComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: ElevationComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let scale = component_param.camera.scale();
+
+        // Draws the shaded tiles.
+        canvas.draw(&self.array, draw_param.scale(vec2(scale, scale)));
+
+        Ok(())
+    }
+
+    fn update(&mut self, _update_param: ()) -> Result<(), ggez::GameError> {
+        // The robot has discovered new tiles, so the shading is refreshed.
+        if self.enabled {
+            self.rebuild();
+        }
+
+        Ok(())
+    }
+}
+
+impl ElevationComponentParam {
+    /// The constructor creates a new instance of the elevation component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+/// The function maps a height between 0.0 (lowest) and 1.0 (highest) to the elevation scale.
+fn elevation_color(height: f32) -> Color {
+    let position = height.clamp(0.0, 1.0) * (COLOR_STOPS.len() - 1) as f32;
+    let index = (position.floor() as usize).min(COLOR_STOPS.len() - 2);
+    let t = position - index as f32;
+
+    let from = Color::from_rgb_u32(COLOR_STOPS[index]);
+    let to = Color::from_rgb_u32(COLOR_STOPS[index + 1]);
+
+    Color::new(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+        0.85,
+    )
+}
@@ -0,0 +1,267 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, Image, Quad, Rect, Text, TextFragment},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    layout::{Layout, Panel},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// Width of the filter panel.
+const PANEL_WIDTH: f32 = 300.0;
+
+/// Height of the title of the filter panel.
+const HEADER_HEIGHT: f32 = 40.0;
+
+/// Height of a single entry of the filter panel.
+const ROW_HEIGHT: f32 = 28.0;
+
+/// A content kind listed by the filter panel.
+struct FilterEntry {
+    texture: Texture,
+    image: Image,
+    known: usize,
+    total: usize,
+}
+
+/// The filter component implements Component and lists the content kinds of the world, with
+/// how many of them are known by the robot and how many exist. Clicking an entry selects it, so
+/// that the contents map highlights the matching tiles.
+pub(in crate::visualizer) struct FilterComponent {
+    map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    entries: Vec<FilterEntry>,
+    selected: HashSet<Texture>,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct FilterComponentParam {
+    layout: Layout,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum FilterComponentUpdateType<'a> {
+    /// The tiles known by the robot have changed, so the contents are counted again.
+    WorldVisibility(&'a [Vec<Option<Tile>>]),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct FilterComponentUpdateParam<'a> {
+    _type: FilterComponentUpdateType<'a>,
+}
+
+impl FilterComponent {
+    /// The constructor creates a new instance of the filter component from the shared reference
+    /// to the map. The panel is hidden by default.
+    pub(crate) fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    ) -> Self {
+        let entries = Texture::get_contents()
+            .into_iter()
+            .map(|texture| FilterEntry {
+                texture,
+                image: texture.get_image(gfx),
+                known: 0,
+                total: 0,
+            })
+            .collect();
+
+        let mut filter = Self {
+            map_rc,
+            entries,
+            selected: HashSet::new(),
+            enabled: false,
+        };
+
+        filter.count(&[]);
+        filter
+    }
+
+    /// The function shows or hides the panel and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function selects the given content kind, or deselects it if it was already selected,
+    /// and returns whether it's selected.
+    pub(crate) fn select(&mut self, texture: Texture) -> bool {
+        if !self.selected.remove(&texture) {
+            self.selected.insert(texture);
+        }
+
+        self.selected.contains(&texture)
+    }
+
+    /// The function returns the selected content kinds.
+    pub(crate) fn selected(&self) -> HashSet<Texture> {
+        self.selected.clone()
+    }
+
+    /// The function returns the size of the panel, if it's visible.
+    pub(crate) fn size(&self) -> Option<Vec2> {
+        self.enabled.then(|| {
+            vec2(
+                PANEL_WIDTH,
+                HEADER_HEIGHT + ROW_HEIGHT * self.entries.len() as f32 + 10.0,
+            )
+        })
+    }
+
+    /// The function returns the content kind of the entry under the given point of the layout,
+    /// if the panel is visible.
+    pub(crate) fn entry_at(&self, point: Vec2, layout: &Layout) -> Option<Texture> {
+        let point = point - layout.position(Panel::Filter)?;
+        if point.x < 0.0 || point.x > PANEL_WIDTH {
+            return None;
+        }
+
+        let row = (point.y - HEADER_HEIGHT) / ROW_HEIGHT;
+
+        if row < 0.0 {
+            None
+        } else {
+            self.entries.get(row as usize).map(|entry| entry.texture)
+        }
+    }
+
+    /// The function counts the contents of every kind in the map and in the given known world.
+    fn count(&mut self, world: &[Vec<Option<Tile>>]) {
+        let map = self.map_rc.borrow();
+
+        for entry in self.entries.iter_mut() {
+            entry.known = 0;
+            entry.total = 0;
+        }
+
+        let mut add = |tile: &Tile, known: bool| {
+            if let Some(texture) = Texture::from_content(&tile.content) {
+                if let Some(entry) = self
+                    .entries
+                    .iter_mut()
+                    .find(|entry| entry.texture == texture)
+                {
+                    if known {
+                        entry.known += 1;
+                    } else {
+                        entry.total += 1;
+                    }
+                }
+            }
+        };
+
+        map.iter().flatten().for_each(|tile| add(tile, false));
+        world
+            .iter()
+            .flatten()
+            .flatten()
+            .for_each(|tile| add(tile, true));
+    }
+}
+
+impl<'a> Component<FilterComponentParam, FilterComponentUpdateParam<'a>> for FilterComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: FilterComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let (Some(position), Some(size)) =
+            (component_param.layout.position(Panel::Filter), self.size())
+        else {
+            return Ok(());
+        };
+
+        canvas.draw(
+            &Quad,
+            draw_param
+                .dest_rect(Rect::new(position.x, position.y, size.x, size.y))
+                .color(Color::from_rgba_u32(0x000080AA)),
+        );
+
+        let title = Text::new(
+            TextFragment::new("Contents")
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(24.0),
+        );
+        canvas.draw(&title, draw_param.dest(position + vec2(10.0, 8.0)));
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let row = position + vec2(0.0, HEADER_HEIGHT + ROW_HEIGHT * i as f32);
+
+            // Selected entries have a highlighted background.
+            if self.selected.contains(&entry.texture) {
+                canvas.draw(
+                    &Quad,
+                    draw_param
+                        .dest_rect(Rect::new(row.x, row.y, PANEL_WIDTH, ROW_HEIGHT))
+                        .color(Color::from_rgba_u32(0xFFD70066)),
+                );
+            }
+
+            canvas.draw(
+                &entry.image,
+                draw_param.dest(row + vec2(10.0, 1.0)).scale(vec2(1.5, 1.5)),
+            );
+
+            let name = Text::new(
+                TextFragment::new(entry.texture.name())
+                    .font("kode")
+                    .color(Color::WHITE)
+                    .scale(20.0),
+            );
+            canvas.draw(&name, draw_param.dest(row + vec2(45.0, 4.0)));
+
+            let count = Text::new(
+                TextFragment::new(format!("{} / {}", entry.known, entry.total))
+                    .font("kode")
+                    .color(Color::WHITE)
+                    .scale(20.0),
+            );
+            canvas.draw(&count, draw_param.dest(row + vec2(190.0, 4.0)));
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: FilterComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            FilterComponentUpdateType::WorldVisibility(world) => {
+                self.count(world);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FilterComponentParam {
+    /// The constructor creates a new instance of the filter component parameters.
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self { layout }
+    }
+}
+
+impl<'a> FilterComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the filter component update parameters.
+    pub(crate) fn new(_type: FilterComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
+
+/// The function dims the given color, which is used for everything not matching the filter.
+pub(in crate::visualizer) fn dim(color: Color) -> Color {
+    Color::new(color.r * 0.35, color.g * 0.35, color.b * 0.35, color.a)
+}
@@ -0,0 +1,157 @@
+use ggez::{
+    context::Has,
+    glam::vec2,
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, InstanceArray},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// The frontier component implements Component and outlines the frontier of the exploration,
+/// that is the tiles known by the robot which are adjacent to unknown ones.
+pub(in crate::visualizer) struct FrontierComponent {
+    array: InstanceArray,
+    frontier: Vec<(usize, usize, Tile)>,
+    map_len: usize,
+    orientation: Orientation,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct FrontierComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum FrontierComponentUpdateType<'a> {
+    /// The tiles known by the robot have changed, so the frontier is computed again.
+    WorldVisibility(&'a [Vec<Option<Tile>>]),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct FrontierComponentUpdateParam<'a> {
+    _type: FrontierComponentUpdateType<'a>,
+}
+
+impl FrontierComponent {
+    /// The constructor creates a new instance of the frontier component. The outline is hidden
+    /// by default.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>) -> Self {
+        Self {
+            array: InstanceArray::new(gfx, Texture::Grid.get_image(gfx)),
+            frontier: Vec::new(),
+            map_len: 0,
+            orientation: Orientation::North,
+            enabled: false,
+        }
+    }
+
+    /// The function shows or hides the outline of the frontier and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function returns the number of tiles of the frontier.
+    pub(crate) fn size(&self) -> usize {
+        self.frontier.len()
+    }
+
+    /// The function moves the outline to its position for the given orientation of the view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.update_outline();
+    }
+
+    /// The function places the outline on the tiles of the frontier.
+    fn update_outline(&mut self) {
+        self.array.set(self.frontier.iter().map(|(row, col, tile)| {
+            DrawParam::new().dest(Camera::top_face(
+                self.map_len,
+                self.orientation,
+                *row,
+                *col,
+                tile,
+            ))
+        }));
+    }
+}
+
+impl<'a> Component<FrontierComponentParam, FrontierComponentUpdateParam<'a>> for FrontierComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: FrontierComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if self.enabled {
+            canvas.draw(
+                &self.array,
+                draw_param
+                    .scale(vec2(
+                        component_param.camera.scale(),
+                        component_param.camera.scale(),
+                    ))
+                    .color(Color::CYAN),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: FrontierComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            FrontierComponentUpdateType::WorldVisibility(world) => {
+                let map_len = world.len();
+
+                // A known tile is on the frontier if one of its four neighbours is unknown.
+                let frontier = world
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(row, tiles)| {
+                        tiles.iter().enumerate().filter_map(move |(col, tile)| {
+                            tile.as_ref().map(|tile| (row, col, tile))
+                        })
+                    })
+                    .filter(|(row, col, _)| {
+                        [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dr, dc)| {
+                            let r = row.checked_add_signed(*dr).filter(|r| *r < map_len);
+                            let c = col.checked_add_signed(*dc).filter(|c| *c < map_len);
+
+                            matches!((r, c), (Some(r), Some(c)) if world[r][c].is_none())
+                        })
+                    })
+                    .map(|(row, col, tile)| (row, col, tile.clone()))
+                    .collect::<Vec<_>>();
+
+                self.frontier = frontier;
+                self.map_len = map_len;
+                self.update_outline();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FrontierComponentParam {
+    /// The constructor creates a new instance of the frontier component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl<'a> FrontierComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the frontier component update parameters.
+    pub(crate) fn new(_type: FrontierComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
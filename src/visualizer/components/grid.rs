@@ -0,0 +1,311 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, Text, TextFragment, TextLayout},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    fog::Fog,
+    textures::Texture,
+};
+
+use super::{Component, CoordinatedInstance};
+
+/// Minimum distance in pixels between two labels of the grid.
+const LABEL_SPACING: f32 = 64.0;
+
+/// The grid component implements Component and draws the borders of every tile and the
+/// (row, column) coordinates at regular intervals. The interval of the labels grows when the
+/// map is zoomed out.
+/// The borders are colored by the fog of war like their tiles, and follow the elevation of the
+/// tiles changed by the robot.
+/// The labels are placed on every update of the view. The texts of the labels already in the
+/// window are kept, so only the labels entering the window are built again while the camera
+/// moves.
+pub(in crate::visualizer) struct GridComponent {
+    map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    fog_rc: Rc<RefCell<Fog>>,
+    borders: CoordinatedInstance,
+    map_len: usize,
+    orientation: Orientation,
+    enabled: bool,
+    labels: HashMap<(usize, usize), (Vec2, Text)>,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct GridComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum GridComponentUpdateType<'a> {
+    /// The view is drawn with the given camera in a window of the given size.
+    View(Camera, Vec2),
+    /// The tiles in the given coordinates (row, column) have changed from the given previous
+    /// tiles.
+    Changed(&'a [((usize, usize), Tile)]),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct GridComponentUpdateParam<'a> {
+    _type: GridComponentUpdateType<'a>,
+}
+
+impl GridComponent {
+    /// The constructor creates a new instance of the grid component from the shared reference to
+    /// the map, colored by the given fog of war. The grid is disabled by default.
+    pub(crate) fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog_rc: Rc<RefCell<Fog>>,
+    ) -> Self {
+        let map_len = map_rc.borrow().len();
+
+        let mut grid = Self {
+            map_rc,
+            fog_rc,
+            borders: CoordinatedInstance::new(gfx, Texture::Grid.get_image(gfx)),
+            map_len,
+            orientation: Orientation::North,
+            enabled: false,
+            labels: HashMap::new(),
+        };
+
+        for row in 0..map_len {
+            for col in 0..map_len {
+                grid.place_border(row, col);
+            }
+        }
+
+        grid
+    }
+
+    /// The function places the borders of the tiles for the given orientation of the view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+
+        for row in 0..self.map_len {
+            for col in 0..self.map_len {
+                self.place_border(row, col);
+            }
+        }
+    }
+
+    /// The function colors every border by the current state of the fog of war.
+    pub(crate) fn refresh_fog(&mut self) {
+        let fog = self.fog_rc.borrow();
+
+        self.borders
+            .update_all(|(x, y), draw_param| draw_param.color(fog.color(y, x)));
+    }
+
+    /// The function colors the borders of the tiles in the given coordinates (row, column) by
+    /// the current state of the fog of war.
+    pub(crate) fn refresh_fog_at(&mut self, coords: &[(usize, usize)]) {
+        let fog = self.fog_rc.borrow();
+
+        for (y, x) in coords.iter().copied() {
+            self.borders
+                .update((x, y), |draw_param| draw_param.color(fog.color(y, x)));
+        }
+    }
+
+    /// The function places the border of the tile in the given row and column on the top face
+    /// of the tile, which depends on its elevation.
+    fn place_border(&mut self, row: usize, col: usize) {
+        let map = self.map_rc.borrow();
+
+        self.borders.push(
+            (col, row),
+            DrawParam::new()
+                .dest(Camera::top_face(
+                    self.map_len,
+                    self.orientation,
+                    row,
+                    col,
+                    &map[row][col],
+                ))
+                .color(self.fog_rc.borrow().color(row, col)),
+        );
+    }
+
+    /// The function shows or hides the grid and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function returns the interval between two labels for the given scale.
+    fn label_interval(scale: f32) -> usize {
+        // Two labelled tiles on the same row are 2 * interval half tiles apart.
+        let mut interval = 1;
+        while (interval as f32) * Texture::width() * scale < LABEL_SPACING {
+            interval *= 2;
+        }
+        interval
+    }
+
+    /// The function returns the range of rows and the range of columns of the tiles shown in a
+    /// window of the given size, padded by a tile on every side.
+    fn visible_range(
+        &self,
+        camera: &Camera,
+        window_size: Vec2,
+    ) -> ((usize, usize), (usize, usize)) {
+        let corners = [
+            vec2(0.0, 0.0),
+            vec2(window_size.x, 0.0),
+            vec2(0.0, window_size.y),
+            window_size,
+        ];
+
+        // The view may be rotated, so every corner of the window can bound the rows or columns.
+        let (min, max) = corners.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), corner| {
+                let (row, col) = Camera::world_to_tile(
+                    self.map_len,
+                    camera.orientation(),
+                    camera.screen_to_world(*corner) - Camera::face_center(),
+                );
+                (min.min(vec2(row, col)), max.max(vec2(row, col)))
+            },
+        );
+
+        let last = self.map_len as f32 - 1.0;
+        let bound = |value: f32| value.clamp(0.0, last.max(0.0)) as usize;
+
+        (
+            (bound(min.x.floor() - 1.0), bound(max.x.ceil() + 1.0)),
+            (bound(min.y.floor() - 1.0), bound(max.y.ceil() + 1.0)),
+        )
+    }
+
+    /// The function places the labels of the visible tiles at the interval of the scale of the
+    /// given camera.
+    fn update_labels(&mut self, camera: Camera, window_size: Vec2) {
+        let interval = Self::label_interval(camera.scale());
+        let ((first_row, last_row), (first_col, last_col)) =
+            self.visible_range(&camera, window_size);
+
+        // The labels of the previous update are reused, while the ones left out of the window
+        // are dropped.
+        let mut cached = std::mem::take(&mut self.labels);
+
+        // The ranges start on a multiple of the interval, so the labels don't move while panning.
+        for row in (first_row / interval * interval..=last_row).step_by(interval) {
+            for col in (first_col / interval * interval..=last_col).step_by(interval) {
+                // Labels are centered on the top face of a block.
+                let position = Camera::tile_to_world(
+                    self.map_len,
+                    camera.orientation(),
+                    row as f32,
+                    col as f32,
+                ) + Camera::face_center();
+                let screen_position = camera.world_to_screen(position);
+
+                if screen_position.x < -LABEL_SPACING
+                    || screen_position.y < -LABEL_SPACING
+                    || screen_position.x > window_size.x
+                    || screen_position.y > window_size.y
+                {
+                    continue;
+                }
+
+                let text = cached
+                    .remove(&(row, col))
+                    .map(|(_, text)| text)
+                    .unwrap_or_else(|| Self::label(row, col));
+
+                self.labels.insert((row, col), (position, text));
+            }
+        }
+    }
+
+    /// The function creates the label of the tile in the given row and column.
+    fn label(row: usize, col: usize) -> Text {
+        let mut text = Text::new(
+            TextFragment::new(format!("{},{}", row, col))
+                .font("kode")
+                .color(Color::BLACK)
+                .scale(14.0),
+        );
+        text.set_layout(TextLayout::center());
+        text
+    }
+}
+
+impl<'a> Component<GridComponentParam, GridComponentUpdateParam<'a>> for GridComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: GridComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let scale = component_param.camera.scale();
+
+        // Fades the borders out when the tiles get too small to read them.
+        let alpha = ((scale - 0.5) / 2.0).clamp(0.0, 0.5);
+        if alpha > 0.0 {
+            canvas.draw(
+                self.borders.array(),
+                draw_param
+                    .scale(vec2(scale, scale))
+                    .color(Color::new(1.0, 1.0, 1.0, alpha)),
+            );
+        }
+
+        // Draws the labels placed by the last update of the view.
+        for (position, text) in self.labels.values() {
+            canvas.draw(text, draw_param.dest(*position * scale));
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: GridComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            GridComponentUpdateType::View(camera, window_size) => {
+                if self.enabled {
+                    self.update_labels(camera, window_size);
+                }
+            }
+            GridComponentUpdateType::Changed(changes) => {
+                for ((row, col), previous) in changes {
+                    let elevation = self.map_rc.borrow()[*row][*col].elevation;
+
+                    if previous.elevation != elevation {
+                        self.place_border(*row, *col);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl GridComponentParam {
+    /// The constructor creates a new instance of the grid component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl<'a> GridComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the grid component update parameters.
+    pub(crate) fn new(_type: GridComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use ggez::{
+    context::Has,
+    glam::vec2,
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, InstanceArray},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// The metric shown by the heatmap.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum HeatmapMetric {
+    /// Number of times the robot entered the tile.
+    Visits,
+    /// Number of ticks the robot spent on the tile.
+    Ticks,
+}
+
+/// The counters of a single tile of the heatmap.
+struct HeatCell {
+    visits: usize,
+    ticks: usize,
+    /// The tile as it was when the robot last entered it.
+    tile: Tile,
+}
+
+/// The heatmap component implements Component and colors every tile reached by the robot by
+/// how often it was visited, from blue (rarely) to red (often).
+pub(in crate::visualizer) struct HeatmapComponent {
+    array: InstanceArray,
+    cells: HashMap<(usize, usize), HeatCell>,
+    position: (usize, usize),
+    map_len: usize,
+    orientation: Orientation,
+    metric: Option<HeatmapMetric>,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct HeatmapComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum HeatmapComponentUpdateType {
+    /// The robot has moved into the given tile.
+    Moved(Tile, (usize, usize)),
+    /// A tick has passed with the robot on its current tile.
+    Tick,
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct HeatmapComponentUpdateParam {
+    _type: HeatmapComponentUpdateType,
+}
+
+impl HeatmapComponent {
+    /// The constructor creates a new instance of the heatmap component, with the robot on the
+    /// given tile. The heatmap is hidden by default.
+    pub(crate) fn new(
+        gfx: &impl Has<GraphicsContext>,
+        initial_tile: &Tile,
+        initial_position: (usize, usize),
+        map_len: usize,
+    ) -> Self {
+        let mut heatmap = Self {
+            array: InstanceArray::new(gfx, Texture::Highlight.get_image(gfx)),
+            cells: HashMap::new(),
+            position: initial_position,
+            map_len,
+            orientation: Orientation::North,
+            metric: None,
+        };
+
+        heatmap.visit(initial_tile, initial_position);
+        heatmap
+    }
+
+    /// The function cycles between visits, ticks and the hidden heatmap, and returns the new
+    /// metric.
+    pub(crate) fn cycle_metric(&mut self) -> Option<HeatmapMetric> {
+        self.metric = match self.metric {
+            None => Some(HeatmapMetric::Visits),
+            Some(HeatmapMetric::Visits) => Some(HeatmapMetric::Ticks),
+            Some(HeatmapMetric::Ticks) => None,
+        };
+
+        self.rebuild();
+        self.metric
+    }
+
+    /// The function moves the colored tiles to their position for the given orientation of the
+    /// view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.rebuild();
+    }
+
+    /// The function counts a visit of the robot on the given tile.
+    fn visit(&mut self, tile: &Tile, coords: (usize, usize)) {
+        let cell = self.cells.entry(coords).or_insert(HeatCell {
+            visits: 0,
+            ticks: 0,
+            tile: tile.clone(),
+        });
+
+        cell.visits += 1;
+        cell.tile = tile.clone();
+        self.position = coords;
+    }
+
+    /// The function rebuilds the instance array from the counters of the current metric.
+    fn rebuild(&mut self) {
+        let Some(metric) = self.metric else {
+            self.array.clear();
+            return;
+        };
+
+        let value = |cell: &HeatCell| match metric {
+            HeatmapMetric::Visits => cell.visits,
+            HeatmapMetric::Ticks => cell.ticks,
+        };
+
+        // Values are normalized on the highest one, with a logarithmic scale so that a single
+        // hot spot doesn't flatten all the other tiles.
+        let highest = self.cells.values().map(value).max().unwrap_or(0);
+        let highest = ((highest + 1) as f32).ln().max(f32::EPSILON);
+
+        self.array
+            .set(self.cells.iter().filter(|(_, cell)| value(cell) > 0).map(
+                |((row, col), cell)| {
+                    let heat = ((value(cell) + 1) as f32).ln() / highest;
+                    let position =
+                        Camera::top_face(self.map_len, self.orientation, *row, *col, &cell.tile);
+
+                    DrawParam::new().dest(position).color(heat_color(heat))
+                },
+            ));
+    }
+}
+
+impl Component<HeatmapComponentParam, HeatmapComponentUpdateParam> for HeatmapComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: HeatmapComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if self.metric.is_some() {
+            canvas.draw(
+                &self.array,
+                draw_param.scale(vec2(
+                    component_param.camera.scale(),
+                    component_param.camera.scale(),
+                )),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, update_param: HeatmapComponentUpdateParam) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            HeatmapComponentUpdateType::Moved(tile, coords) => {
+                self.visit(&tile, coords);
+            }
+            HeatmapComponentUpdateType::Tick => {
+                if let Some(cell) = self.cells.get_mut(&self.position) {
+                    cell.ticks += 1;
+                }
+            }
+        }
+
+        // The instance array is rebuilt only when it's visible.
+        if self.metric.is_some() {
+            self.rebuild();
+        }
+
+        Ok(())
+    }
+}
+
+impl HeatmapComponentParam {
+    /// The constructor creates a new instance of the heatmap component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl HeatmapComponentUpdateParam {
+    /// The constructor creates a new instance of the heatmap component update parameters.
+    pub(crate) fn new(_type: HeatmapComponentUpdateType) -> Self {
+        Self { _type }
+    }
+}
+
+/// The function maps a heat between 0.0 and 1.0 to a color going from blue to green, yellow
+/// and red.
+fn heat_color(heat: f32) -> Color {
+    let heat = heat.clamp(0.0, 1.0);
+
+    let (r, g, b) = if heat < 0.33 {
+        (0.0, heat / 0.33, 1.0 - heat / 0.33)
+    } else if heat < 0.66 {
+        ((heat - 0.33) / 0.33, 1.0, 0.0)
+    } else {
+        (1.0, 1.0 - (heat - 0.66) / 0.34, 0.0)
+    };
+
+    Color::new(r, g, b, 0.6)
+}
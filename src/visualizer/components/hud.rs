@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{
+        Color, DrawMode, FillOptions, GraphicsContext, Image, Mesh, MeshBuilder, Rect,
+        StrokeOptions, Text, TextFragment,
+    },
+};
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+
+use crate::visualizer::{
+    camera::FollowMode,
+    layout::{Layout, Panel},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// Width of the HUD box.
+const HUD_WIDTH: f32 = 400.0;
+
+/// Minimum height of the HUD box, which fits the weather icon.
+const MIN_HEIGHT: f32 = 100.0;
+
+/// The HUD component implements Component and displays a persistent box with the clock, the
+/// day and the current weather of the world, the size of the exploration frontier and the mode
+/// of the camera.
+/// The height of the box follows the measured height of its text.
+pub(in crate::visualizer) struct HudComponent {
+    mesh: Mesh,
+    height: f32,
+    text: Text,
+    weather_images: HashMap<Texture, Image>,
+    weather: WeatherType,
+    time: String,
+    day: usize,
+    frontier: usize,
+    follow_mode: FollowMode,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct HudComponentParam {
+    layout: Layout,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum HudComponentUpdateType {
+    TimeChanged(EnvironmentalConditions),
+    DayChanged(EnvironmentalConditions),
+    Frontier(usize),
+    FollowMode(FollowMode),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct HudComponentUpdateParam {
+    _type: HudComponentUpdateType,
+}
+
+impl HudComponent {
+    /// The constructor creates a new instance of the HUD component from the initial
+    /// environmental conditions of the world and the initial mode of the camera.
+    pub(crate) fn new(
+        gfx: &impl Has<GraphicsContext>,
+        environment: &EnvironmentalConditions,
+        follow_mode: FollowMode,
+    ) -> Self {
+        // Loads an image for every possible weather, so that updates don't need the context.
+        let weather_images = Texture::get_weathers()
+            .iter()
+            .map(|texture| (*texture, texture.get_image(gfx)))
+            .collect::<HashMap<_, _>>();
+
+        // The font has been loaded previously in the main file
+        let mut text = Text::new(
+            TextFragment::new(String::new())
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(30.0),
+        );
+
+        // Sets the text bounds and wrap. The text is never cut, as the box grows with it.
+        text.set_bounds(vec2(HUD_WIDTH - 110.0, f32::INFINITY));
+        text.set_wrap(true);
+
+        let mut hud = Self {
+            mesh: Self::build_mesh(gfx, MIN_HEIGHT),
+            height: MIN_HEIGHT,
+            text,
+            weather_images,
+            weather: environment.get_weather_condition(),
+            time: environment.get_time_of_day_string(),
+            day: 1,
+            frontier: 0,
+            follow_mode,
+        };
+
+        hud.update_text();
+        hud.fit(gfx);
+        hud
+    }
+
+    /// The function measures the text of the HUD and fits the height of the box to it.
+    pub(crate) fn fit(&mut self, gfx: &impl Has<GraphicsContext>) {
+        let text_height = self.text.measure(gfx).map(|size| size.y).unwrap_or(0.0);
+        let height = (text_height + 20.0).max(MIN_HEIGHT);
+
+        if height != self.height {
+            self.mesh = Self::build_mesh(gfx, height);
+            self.height = height;
+        }
+    }
+
+    /// The function returns the size of the HUD box.
+    pub(crate) fn size(&self) -> Vec2 {
+        vec2(HUD_WIDTH, self.height)
+    }
+
+    /// The function builds the background mesh of a HUD box of the given height.
+    fn build_mesh(gfx: &impl Has<GraphicsContext>, height: f32) -> Mesh {
+        // Builds background mesh
+        let mut mesh_builder = MeshBuilder::new();
+        mesh_builder
+            .rectangle(
+                DrawMode::Fill(FillOptions::default()),
+                Rect::new(0.0, 0.0, HUD_WIDTH, height),
+                Color::from_rgba_u32(0x000080AA), // It has a bit of transparency
+            )
+            .unwrap();
+        mesh_builder
+            .rectangle(
+                DrawMode::Stroke(StrokeOptions::default().with_line_width(5.0)),
+                Rect::new(0.0, 0.0, HUD_WIDTH, height),
+                Color::from_rgba_u32(0x000051FF),
+            )
+            .unwrap();
+
+        // Creates the mesh
+        Mesh::from_data(gfx, mesh_builder.build())
+    }
+
+    /// The function rewrites the text of the HUD from the current state.
+    fn update_text(&mut self) {
+        self.text.fragments_mut().get_mut(0).unwrap().text = format!(
+            "Day {} - {}\n{}\nFrontier {}\n{}",
+            self.day,
+            self.time,
+            weather_name(&self.weather),
+            self.frontier,
+            self.follow_mode.name()
+        );
+    }
+}
+
+impl Component<HudComponentParam, HudComponentUpdateParam> for HudComponent {
+    fn draw(
+        &self,
+        canvas: &mut ggez::graphics::Canvas,
+        draw_param: ggez::graphics::DrawParam,
+        component_param: HudComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let Some(position) = component_param.layout.position(Panel::Hud) else {
+            return Ok(());
+        };
+
+        // Draws background mesh
+        canvas.draw(&self.mesh, draw_param.dest(position));
+
+        // Draws weather icon
+        if let Some(image) = self
+            .weather_images
+            .get(&Texture::from_weather(&self.weather))
+        {
+            canvas.draw(
+                image,
+                draw_param
+                    .dest(position + vec2(18.0, 18.0))
+                    .scale(vec2(4.0, 4.0)),
+            );
+        }
+
+        // Draws HUD text
+        canvas.draw(&self.text, draw_param.dest(position + vec2(100.0, 10.0)));
+
+        Ok(())
+    }
+
+    fn update(&mut self, update_param: HudComponentUpdateParam) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            HudComponentUpdateType::TimeChanged(environment) => {
+                self.time = environment.get_time_of_day_string();
+                self.weather = environment.get_weather_condition();
+            }
+            HudComponentUpdateType::DayChanged(environment) => {
+                self.day += 1;
+                self.time = environment.get_time_of_day_string();
+                self.weather = environment.get_weather_condition();
+            }
+            HudComponentUpdateType::Frontier(frontier) => {
+                self.frontier = frontier;
+            }
+            HudComponentUpdateType::FollowMode(follow_mode) => {
+                self.follow_mode = follow_mode;
+            }
+        }
+
+        self.update_text();
+
+        Ok(())
+    }
+}
+
+impl HudComponentParam {
+    /// The constructor creates a new instance of the HUD component parameters.
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self { layout }
+    }
+}
+
+impl HudComponentUpdateParam {
+    /// The constructor creates a new instance of the HUD component update parameters.
+    pub(crate) fn new(_type: HudComponentUpdateType) -> Self {
+        Self { _type }
+    }
+}
+
+/// The function returns the human readable name of the given weather.
+fn weather_name(weather: &WeatherType) -> &'static str {
+    match weather {
+        WeatherType::Sunny => "Sunny",
+        WeatherType::Rainy => "Rainy",
+        WeatherType::Foggy => "Foggy",
+        WeatherType::TropicalMonsoon => "Tropical monsoon",
+        WeatherType::TrentinoSnow => "Trentino snow",
+    }
+}
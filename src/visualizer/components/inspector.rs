@@ -0,0 +1,225 @@
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{
+        Canvas, Color, DrawMode, DrawParam, GraphicsContext, Mesh, Quad, Rect, Text, TextFragment,
+    },
+};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::visualizer::{camera::Camera, layout::Layout};
+
+use super::Component;
+
+/// Width of the tooltip box.
+const TOOLTIP_WIDTH: f32 = 380.0;
+
+/// Height of a line of the tooltip text.
+const LINE_HEIGHT: f32 = 26.0;
+
+/// The struct contains the tile under the mouse cursor.
+pub(in crate::visualizer) struct HoveredTile {
+    coords: (usize, usize),
+    tile: Tile,
+    discovered: bool,
+}
+
+/// The inspector component implements Component. It outlines the tile under the mouse cursor
+/// and shows a tooltip with its details, drawn on the screen-space pass.
+pub(in crate::visualizer) struct InspectorComponent {
+    outline: Mesh,
+    text: Text,
+    map_len: usize,
+    hovered: Option<HoveredTile>,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct InspectorComponentParam {
+    camera: Camera,
+    cursor: Option<Vec2>,
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct InspectorComponentUpdateParam {
+    hovered: Option<HoveredTile>,
+}
+
+impl InspectorComponent {
+    /// The constructor creates a new instance of the inspector component for a map of the given
+    /// size.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>, map_len: usize) -> Self {
+        // The outline follows the top face of a block, relative to the top left corner of its
+        // image.
+        let center = Camera::face_center();
+        let outline = Mesh::new_polygon(
+            gfx,
+            DrawMode::stroke(0.5),
+            &[
+                vec2(center.x, 1.0),
+                vec2(center.x * 2.0, center.y),
+                vec2(center.x, center.y * 2.0 - 1.0),
+                vec2(0.0, center.y),
+            ],
+            Color::YELLOW,
+        )
+        .unwrap();
+
+        // The font has been loaded previously in the main file
+        let mut text = Text::new(
+            TextFragment::new(String::new())
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(22.0),
+        );
+
+        text.set_bounds(vec2(TOOLTIP_WIDTH - 20.0, f32::INFINITY));
+        text.set_wrap(true);
+
+        Self {
+            outline,
+            text,
+            map_len,
+            hovered: None,
+        }
+    }
+
+    /// The function draws the tooltip with the details of the hovered tile next to the given
+    /// cursor, in physical pixels of the window. The tooltip is kept inside the given layout.
+    pub(crate) fn draw_tooltip(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        layout: &Layout,
+        cursor: Option<Vec2>,
+    ) {
+        let (Some(_), Some(cursor)) = (&self.hovered, cursor) else {
+            return;
+        };
+
+        let lines = self.text.fragments()[0].text.lines().count() as f32;
+        let size = vec2(TOOLTIP_WIDTH, lines * LINE_HEIGHT + 20.0);
+        let tooltip = layout.clamp(layout.to_logical(cursor) + vec2(20.0, 20.0), size);
+
+        canvas.draw(
+            &Quad,
+            draw_param
+                .dest_rect(Rect::new(tooltip.x, tooltip.y, size.x, size.y))
+                .color(Color::from_rgba_u32(0x000080DD)),
+        );
+
+        canvas.draw(&self.text, draw_param.dest(tooltip + vec2(10.0, 10.0)));
+    }
+}
+
+impl Component<InspectorComponentParam, InspectorComponentUpdateParam> for InspectorComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: InspectorComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let (Some(hovered), Some(_)) = (&self.hovered, component_param.cursor) else {
+            return Ok(());
+        };
+
+        let scale = component_param.camera.scale();
+
+        // Draws the outline over the top face of the hovered tile.
+        let position = Camera::top_face(
+            self.map_len,
+            component_param.camera.orientation(),
+            hovered.coords.0,
+            hovered.coords.1,
+            &hovered.tile,
+        );
+
+        canvas.draw(
+            &self.outline,
+            draw_param.dest(position * scale).scale(vec2(scale, scale)),
+        );
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: InspectorComponentUpdateParam,
+    ) -> Result<(), ggez::GameError> {
+        if let Some(hovered) = &update_param.hovered {
+            self.text.fragments_mut().get_mut(0).unwrap().text = format!(
+                "Tile {:?}\nType: {}\nElevation: {}\nContent: {}\nDiscovered: {}",
+                hovered.coords,
+                tile_type_name(&hovered.tile.tile_type),
+                hovered.tile.elevation,
+                content_description(&hovered.tile.content),
+                if hovered.discovered { "yes" } else { "no" }
+            );
+        }
+
+        self.hovered = update_param.hovered;
+
+        Ok(())
+    }
+}
+
+impl HoveredTile {
+    /// The constructor creates a new instance of the hovered tile.
+    pub(crate) fn new(coords: (usize, usize), tile: Tile, discovered: bool) -> Self {
+        Self {
+            coords,
+            tile,
+            discovered,
+        }
+    }
+}
+
+impl InspectorComponentParam {
+    /// The constructor creates a new instance of the inspector component parameters.
+    pub(crate) fn new(camera: Camera, cursor: Option<Vec2>) -> Self {
+        Self { camera, cursor }
+    }
+}
+
+impl InspectorComponentUpdateParam {
+    /// The constructor creates a new instance of the inspector component update parameters.
+    pub(crate) fn new(hovered: Option<HoveredTile>) -> Self {
+        Self { hovered }
+    }
+}
+
+/// The function returns the name of the tile type, including the state of teleports.
+pub(super) fn tile_type_name(tile_type: &TileType) -> String {
+    match tile_type {
+        TileType::Teleport(true) => "Teleport (active)".to_string(),
+        TileType::Teleport(false) => "Teleport (inactive)".to_string(),
+        tile_type => format!("{:?}", tile_type),
+    }
+}
+
+/// The function returns the name of the content followed by its quantity.
+pub(super) fn content_description(content: &Content) -> String {
+    let name = match content {
+        Content::Rock(_) => "Rock",
+        Content::Tree(_) => "Tree",
+        Content::Garbage(_) => "Garbage",
+        Content::Fire => "Fire",
+        Content::Coin(_) => "Coin",
+        Content::Bin(_) => "Bin",
+        Content::Crate(_) => "Crate",
+        Content::Bank(_) => "Bank",
+        Content::Water(_) => "Water",
+        Content::Market(_) => "Market",
+        Content::Fish(_) => "Fish",
+        Content::Building => "Building",
+        Content::Bush(_) => "Bush",
+        Content::JollyBlock(_) => "Jolly block",
+        Content::Scarecrow => "Scarecrow",
+        Content::None => "None",
+    };
+
+    match content.get_value() {
+        (Some(quantity), _) => format!("{} x{}", name, quantity),
+        (None, Some(range)) => format!("{} ({}..{})", name, range.start, range.end),
+        (None, None) => name.to_string(),
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{HashMap, HashSet};
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, Image, Quad, Rect, Text, TextFragment},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    layout::{Layout, Panel},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// Width of the legend panel.
+const PANEL_WIDTH: f32 = 400.0;
+
+/// Height of the title of a group of the legend.
+const HEADER_HEIGHT: f32 = 36.0;
+
+/// Height of a single entry of the legend.
+const ROW_HEIGHT: f32 = 26.0;
+
+/// Horizontal position of the half tile column of the terrain entries.
+const HALF_COLUMN: f32 = 110.0;
+
+/// Horizontal position of the names of the entries.
+const NAME_COLUMN: f32 = 210.0;
+
+/// The legend component implements Component and lists the textures of the map with their
+/// sprite, their name and how many of them are known by the robot.
+/// Terrains are grouped in pairs of block and half tile, followed by the contents. Clicking an
+/// entry shows or hides its texture on the map.
+pub(in crate::visualizer) struct LegendComponent {
+    images: HashMap<Texture, Image>,
+    terrains: Vec<(Texture, Texture)>,
+    contents: Vec<Texture>,
+    counts: HashMap<Texture, usize>,
+    hidden: HashSet<Texture>,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct LegendComponentParam {
+    layout: Layout,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum LegendComponentUpdateType<'a> {
+    /// The tiles known by the robot have changed, so the textures are counted again.
+    WorldVisibility(&'a [Vec<Option<Tile>>]),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct LegendComponentUpdateParam<'a> {
+    _type: LegendComponentUpdateType<'a>,
+}
+
+impl LegendComponent {
+    /// The constructor creates a new instance of the legend component. The panel is hidden by
+    /// default.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>) -> Self {
+        // Blocks are listed before their half tile.
+        let terrains = Texture::get_blocks()
+            .chunks(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        let contents = Texture::get_contents();
+
+        let images = Texture::get_blocks()
+            .into_iter()
+            .chain(contents.iter().copied())
+            .map(|texture| (texture, texture.get_image(gfx)))
+            .collect();
+
+        Self {
+            images,
+            terrains,
+            contents,
+            counts: HashMap::new(),
+            hidden: HashSet::new(),
+            enabled: false,
+        }
+    }
+
+    /// The function shows or hides the panel and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function shows the given textures if any of them is hidden, otherwise it hides all of
+    /// them. It returns whether the textures are visible.
+    pub(crate) fn toggle_textures(&mut self, textures: &[Texture]) -> bool {
+        let visible = textures.iter().any(|texture| self.hidden.contains(texture));
+
+        for texture in textures {
+            if visible {
+                self.hidden.remove(texture);
+            } else {
+                self.hidden.insert(*texture);
+            }
+        }
+
+        visible
+    }
+
+    /// The function returns the textures hidden from the map.
+    pub(crate) fn hidden(&self) -> HashSet<Texture> {
+        self.hidden.clone()
+    }
+
+    /// The function returns the vertical position of the contents, relative to the panel.
+    fn contents_top(&self) -> f32 {
+        HEADER_HEIGHT * 2.0 + ROW_HEIGHT * self.terrains.len() as f32
+    }
+
+    /// The function returns the size of the panel, if it's visible.
+    pub(crate) fn size(&self) -> Option<Vec2> {
+        self.enabled.then(|| {
+            vec2(
+                PANEL_WIDTH,
+                self.contents_top() + ROW_HEIGHT * self.contents.len() as f32 + 10.0,
+            )
+        })
+    }
+
+    /// The function returns the textures of the entry under the given point of the layout, if
+    /// the panel is visible.
+    /// The name of a terrain refers to both its block and its half tile.
+    pub(crate) fn entry_at(&self, point: Vec2, layout: &Layout) -> Option<Vec<Texture>> {
+        let point = point - layout.position(Panel::Legend)?;
+        if point.x < 0.0 || point.x > PANEL_WIDTH {
+            return None;
+        }
+
+        let terrain_row = (point.y - HEADER_HEIGHT) / ROW_HEIGHT;
+        let contents_top = self.contents_top();
+        let content_row = (point.y - contents_top) / ROW_HEIGHT;
+
+        if terrain_row >= 0.0 && (terrain_row as usize) < self.terrains.len() {
+            let (block, half) = self.terrains[terrain_row as usize];
+
+            Some(if point.x < HALF_COLUMN {
+                vec![block]
+            } else if point.x < NAME_COLUMN {
+                vec![half]
+            } else {
+                vec![block, half]
+            })
+        } else if content_row >= 0.0 {
+            self.contents
+                .get(content_row as usize)
+                .map(|texture| vec![*texture])
+        } else {
+            None
+        }
+    }
+
+    /// The function draws the sprite and the count of a texture at the given position.
+    fn draw_entry(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        texture: Texture,
+        position: Vec2,
+    ) {
+        // Hidden textures are drawn transparent.
+        let color = if self.hidden.contains(&texture) {
+            Color::new(1.0, 1.0, 1.0, 0.3)
+        } else {
+            Color::WHITE
+        };
+
+        canvas.draw(
+            &self.images[&texture],
+            draw_param
+                .dest(position + vec2(10.0, 0.0))
+                .scale(vec2(1.5, 1.5))
+                .color(color),
+        );
+
+        let count = Text::new(
+            TextFragment::new(self.counts.get(&texture).unwrap_or(&0).to_string())
+                .font("kode")
+                .color(color)
+                .scale(18.0),
+        );
+        canvas.draw(&count, draw_param.dest(position + vec2(40.0, 4.0)));
+    }
+
+    /// The function draws the title of a group of the legend at the given position.
+    fn draw_header(canvas: &mut Canvas, draw_param: DrawParam, title: &str, position: Vec2) {
+        let text = Text::new(
+            TextFragment::new(title)
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(24.0),
+        );
+        canvas.draw(&text, draw_param.dest(position + vec2(10.0, 6.0)));
+    }
+
+    /// The function draws the name of an entry at the given position.
+    fn draw_name(canvas: &mut Canvas, draw_param: DrawParam, name: &str, position: Vec2) {
+        let text = Text::new(
+            TextFragment::new(name)
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(18.0),
+        );
+        canvas.draw(&text, draw_param.dest(position + vec2(NAME_COLUMN, 4.0)));
+    }
+}
+
+impl<'a> Component<LegendComponentParam, LegendComponentUpdateParam<'a>> for LegendComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: LegendComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let (Some(position), Some(size)) =
+            (component_param.layout.position(Panel::Legend), self.size())
+        else {
+            return Ok(());
+        };
+        let contents_top = self.contents_top();
+
+        canvas.draw(
+            &Quad,
+            draw_param
+                .dest_rect(Rect::new(position.x, position.y, size.x, size.y))
+                .color(Color::from_rgba_u32(0x000080AA)),
+        );
+
+        // Draws the terrains, with the block and the half tile on the same row.
+        Self::draw_header(canvas, draw_param, "Terrain (block / half)", position);
+
+        for (i, (block, half)) in self.terrains.iter().enumerate() {
+            let row = position + vec2(0.0, HEADER_HEIGHT + ROW_HEIGHT * i as f32);
+
+            self.draw_entry(canvas, draw_param, *block, row);
+            self.draw_entry(canvas, draw_param, *half, row + vec2(HALF_COLUMN, 0.0));
+            Self::draw_name(canvas, draw_param, block.name(), row);
+        }
+
+        // Draws the contents.
+        Self::draw_header(
+            canvas,
+            draw_param,
+            "Contents",
+            position + vec2(0.0, contents_top - HEADER_HEIGHT),
+        );
+
+        for (i, texture) in self.contents.iter().enumerate() {
+            let row = position + vec2(0.0, contents_top + ROW_HEIGHT * i as f32);
+
+            self.draw_entry(canvas, draw_param, *texture, row);
+            Self::draw_name(canvas, draw_param, texture.name(), row);
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: LegendComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            LegendComponentUpdateType::WorldVisibility(world) => {
+                self.counts.clear();
+
+                for tile in world.iter().flatten().flatten() {
+                    *self.counts.entry(Texture::from_tile(tile)).or_insert(0) += 1;
+
+                    if let Some(texture) = Texture::from_content(&tile.content) {
+                        *self.counts.entry(texture).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LegendComponentParam {
+    /// The constructor creates a new instance of the legend component parameters.
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self { layout }
+    }
+}
+
+impl<'a> LegendComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the legend component update parameters.
+    pub(crate) fn new(_type: LegendComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
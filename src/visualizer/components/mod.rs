@@ -1,9 +1,30 @@
-use ggez::graphics::{Canvas, DrawParam, InstanceArray};
+use std::collections::HashMap;
+
+use ggez::{
+    context::Has,
+    glam::Vec2,
+    graphics::{Canvas, DrawParam, GraphicsContext, Image, InstanceArray},
+};
+use robotics_lib::world::tile::{Tile, TileType};
 
 pub(super) mod contents_map;
 pub(super) mod dialog;
+pub(super) mod elevation;
+pub(super) mod filter;
+pub(super) mod frontier;
+pub(super) mod grid;
+pub(super) mod heatmap;
+pub(super) mod hud;
+pub(super) mod inspector;
+pub(super) mod legend;
+pub(super) mod particles;
 pub(super) mod player;
+pub(super) mod score;
+pub(super) mod selection;
+pub(super) mod spotlight;
 pub(super) mod tails_map;
+pub(super) mod teleports;
+pub(super) mod trail;
 
 /// A component that can be drawn on a canvas.
 /// It has basic draw and update methods callable from the visualizer.
@@ -22,12 +43,113 @@ pub(super) trait Component<ComponentParam, UpdateParam> {
     }
 }
 
+/// The function tells whether the robot moving between the given coordinates has jumped through
+/// a teleport. Jumps land on a teleport tile, so a long move anywhere else is only a move whose
+/// events have been missed.
+fn teleported(from: (usize, usize), to: (usize, usize), tile: &Tile) -> bool {
+    matches!(tile.tile_type, TileType::Teleport(_))
+        && from.0.abs_diff(to.0) + from.1.abs_diff(to.1) > 1
+}
+
 /// The struct implements the draw params position tracing.
 /// It is used for adding, updating and removing elements from the instance array, which
 /// doesn't provide a method for these operations.
 /// A set of coordinates from elements has the same position in the vector of its
-/// corresponding draw param into the instance array.
+/// corresponding draw param into the instance array, and the index maps the coordinates back to
+/// that position, so every operation on a single tile takes constant time.
+/// Removing an element moves the last one into its slot, like `Vec::swap_remove`. The instance
+/// array can't shrink without being rebuilt, so the slots left at its end are collapsed and
+/// reused by the following pushes. Collapsed slots are never reclaimed: the array keeps the
+/// size of the most elements it has held, and every slot is still drawn with a zero scale.
 pub(self) struct CoordinatedInstance {
     array: InstanceArray,
     elements: Vec<(usize, usize)>,
+    index: HashMap<(usize, usize), usize>,
+}
+
+impl CoordinatedInstance {
+    /// The constructor creates a new instance of the given image, without elements.
+    fn new(gfx: &impl Has<GraphicsContext>, image: Image) -> Self {
+        Self {
+            array: InstanceArray::new(gfx, image),
+            elements: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// The function returns the instance array to be drawn.
+    fn array(&self) -> &InstanceArray {
+        &self.array
+    }
+
+    /// The function returns the coordinates of the elements, in the order of their draw params.
+    fn elements(&self) -> &[(usize, usize)] {
+        &self.elements
+    }
+
+    /// The function returns whether there aren't any elements to draw.
+    fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// The function adds the draw param of the given coordinates, replacing the previous one if
+    /// they already have one.
+    fn push(&mut self, coords: (usize, usize), draw_param: DrawParam) {
+        if let Some(slot) = self.index.get(&coords) {
+            self.array.update(*slot as u32, draw_param);
+            return;
+        }
+
+        let slot = self.elements.len();
+
+        // Reuses a collapsed slot, if any.
+        if slot < self.array.instances().len() {
+            self.array.update(slot as u32, draw_param);
+        } else {
+            self.array.push(draw_param);
+        }
+
+        self.elements.push(coords);
+        self.index.insert(coords, slot);
+    }
+
+    /// The function removes the draw param of the given coordinates and returns it, if any.
+    /// The last element takes its slot.
+    fn swap_remove(&mut self, coords: (usize, usize)) -> Option<DrawParam> {
+        let slot = self.index.remove(&coords)?;
+        let last = self.elements.len() - 1;
+        let draw_param = self.array.instances()[slot];
+
+        if slot != last {
+            let moved = self.array.instances()[last];
+            self.array.update(slot as u32, moved);
+            self.index.insert(self.elements[last], slot);
+        }
+
+        self.elements.swap_remove(slot);
+
+        // Collapses the last slot, which isn't used anymore.
+        self.array
+            .update(last as u32, DrawParam::new().scale(Vec2::ZERO));
+
+        Some(draw_param)
+    }
+
+    /// The function replaces the draw param of the given coordinates with the one returned by
+    /// the given function from the current one, if they have one.
+    fn update(&mut self, coords: (usize, usize), f: impl FnOnce(DrawParam) -> DrawParam) {
+        if let Some(slot) = self.index.get(&coords) {
+            let draw_param = f(self.array.instances()[*slot]);
+            self.array.update(*slot as u32, draw_param);
+        }
+    }
+
+    /// The function replaces the draw param of every element with the one returned by the given
+    /// function, from its coordinates and its current draw param.
+    fn update_all(&mut self, mut f: impl FnMut((usize, usize), DrawParam) -> DrawParam) {
+        for (slot, coords) in self.elements.iter().enumerate() {
+            let draw_param = f(*coords, self.array.instances()[slot]);
+            self.array.update(slot as u32, draw_param);
+        }
+    }
 }
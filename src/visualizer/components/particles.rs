@@ -0,0 +1,271 @@
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, InstanceArray, Quad, Rect},
+};
+use rand::Rng;
+use robotics_lib::world::environmental_conditions::WeatherType;
+
+use super::Component;
+
+/// A single particle of the weather effect, in screen coordinates.
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    size: Vec2,
+    color: Color,
+    phase: f32,
+}
+
+/// The particles component implements Component and draws an animated screen-space effect for
+/// the current weather. It can be disabled to save performance.
+pub(in crate::visualizer) struct ParticlesComponent {
+    array: InstanceArray,
+    particles: Vec<Particle>,
+    weather: WeatherType,
+    window_size: Vec2,
+    flash: f32,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct ParticlesComponentParam {
+    origin: Vec2,
+    window_size: Vec2,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum ParticlesComponentUpdateType {
+    /// The weather has changed, so the particles have to be regenerated.
+    WeatherChanged(WeatherType),
+    /// A frame has passed. It contains the elapsed seconds and the window size.
+    Tick(f32, Vec2),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct ParticlesComponentUpdateParam {
+    _type: ParticlesComponentUpdateType,
+}
+
+impl ParticlesComponent {
+    /// The constructor creates a new instance of the particles component for the given weather.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>, weather: WeatherType) -> Self {
+        Self {
+            // Without an image every particle is a white quad, colored by its draw param.
+            array: InstanceArray::new(gfx, None),
+            particles: Vec::new(),
+            weather,
+            window_size: vec2(0.0, 0.0),
+            flash: 0.0,
+            enabled: true,
+        }
+    }
+
+    /// The function enables or disables the effect and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.particles.clear();
+        self.array.clear();
+        self.enabled
+    }
+
+    /// The function returns the number of particles the current weather needs.
+    fn target_count(&self) -> usize {
+        // The amount of particles grows with the window area, relative to a 1600x1200 window.
+        let density = (self.window_size.x * self.window_size.y) / (1600.0 * 1200.0);
+        let count = match self.weather {
+            WeatherType::Sunny => 0.0,
+            WeatherType::Rainy => 300.0,
+            WeatherType::TropicalMonsoon => 700.0,
+            WeatherType::TrentinoSnow => 250.0,
+            WeatherType::Foggy => 30.0,
+        };
+
+        (count * density) as usize
+    }
+
+    /// The function creates a new particle for the current weather.
+    /// If spread is true, the particle can appear anywhere on the screen, otherwise it appears
+    /// at the top (or the left side for fog) of the window.
+    fn spawn(&self, spread: bool) -> Particle {
+        let mut rng = rand::thread_rng();
+        let width = self.window_size.x.max(1.0);
+        let height = self.window_size.y.max(1.0);
+
+        let (velocity, size, color) = match self.weather {
+            WeatherType::Rainy => (
+                vec2(rng.gen_range(-80.0..-40.0), rng.gen_range(700.0..900.0)),
+                vec2(1.5, rng.gen_range(10.0..16.0)),
+                Color::new(0.7, 0.8, 1.0, 0.6),
+            ),
+            WeatherType::TropicalMonsoon => (
+                vec2(rng.gen_range(-260.0..-180.0), rng.gen_range(1000.0..1300.0)),
+                vec2(2.0, rng.gen_range(14.0..22.0)),
+                Color::new(0.6, 0.7, 0.9, 0.7),
+            ),
+            WeatherType::TrentinoSnow => {
+                let size = rng.gen_range(2.0..5.0);
+                (
+                    vec2(rng.gen_range(-20.0..20.0), rng.gen_range(50.0..110.0)),
+                    vec2(size, size),
+                    Color::new(1.0, 1.0, 1.0, 0.9),
+                )
+            }
+            WeatherType::Foggy => (
+                vec2(rng.gen_range(10.0..35.0), 0.0),
+                vec2(rng.gen_range(250.0..500.0), rng.gen_range(60.0..160.0)),
+                Color::new(0.85, 0.85, 0.9, 0.12),
+            ),
+            WeatherType::Sunny => (vec2(0.0, 0.0), vec2(0.0, 0.0), Color::WHITE),
+        };
+
+        let position = if spread {
+            vec2(rng.gen_range(0.0..width), rng.gen_range(0.0..height))
+        } else if let WeatherType::Foggy = self.weather {
+            vec2(-size.x, rng.gen_range(0.0..height))
+        } else {
+            // Spawns a bit wider than the window, since slanted drops move leftwards.
+            vec2(rng.gen_range(0.0..width * 1.2), -size.y)
+        };
+
+        Particle {
+            position,
+            velocity,
+            size,
+            color,
+            phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        }
+    }
+
+    /// The function moves the particles, respawning the ones which left the window.
+    fn tick(&mut self, delta: f32, window_size: Vec2) {
+        if !self.enabled {
+            return;
+        }
+
+        self.window_size = window_size;
+
+        // Moves every particle. Snowflakes also sway horizontally.
+        for particle in self.particles.iter_mut() {
+            particle.phase += delta * 2.0;
+            particle.position += particle.velocity * delta;
+
+            if let WeatherType::TrentinoSnow = self.weather {
+                particle.position.x += particle.phase.sin() * 20.0 * delta;
+            }
+        }
+
+        // Keeps only the particles still inside the window.
+        self.particles.retain(|particle| {
+            particle.position.x < window_size.x
+                && particle.position.x + particle.size.x > 0.0
+                && particle.position.y < window_size.y
+        });
+
+        // Refills the particles up to the amount required by the weather.
+        let spread = self.particles.is_empty();
+        while self.particles.len() < self.target_count() {
+            let particle = self.spawn(spread);
+            self.particles.push(particle);
+        }
+
+        // Monsoons have random lightnings.
+        self.flash = (self.flash - delta).max(0.0);
+        if let WeatherType::TropicalMonsoon = self.weather {
+            if rand::thread_rng().gen_bool((0.08 * delta as f64).min(1.0)) {
+                self.flash = 0.15;
+            }
+        }
+
+        self.array.set(self.particles.iter().map(|particle| {
+            DrawParam::new()
+                .dest(particle.position)
+                .scale(particle.size)
+                .color(particle.color)
+        }));
+    }
+
+    /// The function returns the color covering the whole screen for the current weather.
+    fn overlay(&self) -> Option<Color> {
+        if self.flash > 0.0 {
+            return Some(Color::new(1.0, 1.0, 1.0, self.flash * 3.0));
+        }
+
+        match self.weather {
+            WeatherType::Foggy => Some(Color::new(0.8, 0.8, 0.85, 0.25)),
+            WeatherType::TropicalMonsoon => Some(Color::new(0.0, 0.0, 0.1, 0.25)),
+            WeatherType::Rainy => Some(Color::new(0.0, 0.0, 0.1, 0.1)),
+            _ => None,
+        }
+    }
+}
+
+impl Component<ParticlesComponentParam, ParticlesComponentUpdateParam> for ParticlesComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: ParticlesComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        // Draws the overlay covering the whole window.
+        if let Some(color) = self.overlay() {
+            canvas.draw(
+                &Quad,
+                draw_param
+                    .dest_rect(Rect::new(
+                        component_param.origin.x,
+                        component_param.origin.y,
+                        component_param.window_size.x,
+                        component_param.window_size.y,
+                    ))
+                    .color(color),
+            );
+        }
+
+        // Draws the particles, which are stored in screen coordinates.
+        canvas.draw(&self.array, draw_param.dest(component_param.origin));
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: ParticlesComponentUpdateParam,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            ParticlesComponentUpdateType::WeatherChanged(weather) => {
+                if std::mem::discriminant(&weather) != std::mem::discriminant(&self.weather) {
+                    self.weather = weather;
+                    self.particles.clear();
+                    self.array.clear();
+                }
+            }
+            ParticlesComponentUpdateType::Tick(delta, window_size) => {
+                self.tick(delta, window_size);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ParticlesComponentParam {
+    /// The constructor creates a new instance of the particles component parameters.
+    pub(crate) fn new(origin: Vec2, window_size: Vec2) -> Self {
+        Self {
+            origin,
+            window_size,
+        }
+    }
+}
+
+impl ParticlesComponentUpdateParam {
+    /// The constructor creates a new instance of the particles component update parameters.
+    pub(crate) fn new(_type: ParticlesComponentUpdateType) -> Self {
+        Self { _type }
+    }
+}
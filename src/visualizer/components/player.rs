@@ -1,42 +1,116 @@
 use ggez::context::Has;
-use ggez::glam::vec2;
+use ggez::glam::{vec2, Vec2};
+use ggez::graphics::Color;
 use ggez::graphics::GraphicsContext;
 use ggez::graphics::Image;
+use robotics_lib::world::tile::Tile;
 
-use crate::visualizer::textures::Texture;
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    textures::Texture,
+};
 
-use super::Component;
+use super::{teleported, Component};
 
 /// The structure contains the state of the player component.
+/// The image of the player slides from the previous tile to the current one, so that it doesn't
+/// snap between the tiles.
 pub(in crate::visualizer) struct PlayerComponent {
     image: Image,
     position: (usize, usize),
+    tile: Tile,
     map_size: (usize, usize),
+    orientation: Orientation,
+    /// Position of the image on the previous tile, in world coordinates.
+    from: Vec2,
+    /// Position of the image on the current tile, in world coordinates.
+    to: Vec2,
+    elapsed: f32,
+    duration: f32,
 }
 
 /// The structure contains parameters required by draw function.
 pub(in crate::visualizer) struct PlayerComponentParam {
-    scale: f32,
+    camera: Camera,
+    tint: Color,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum PlayerComponentUpdateType {
+    /// The robot has moved into the given tile, and the movement lasts the given seconds.
+    Moved(Tile, (usize, usize), f32),
+    /// The given seconds have elapsed since the previous frame.
+    Tick(f32),
 }
 
 /// The structure contains parameters required by update function.
 pub(in crate::visualizer) struct PlayerComponentUpdateParam {
-    position: (usize, usize),
+    _type: PlayerComponentUpdateType,
 }
 
 impl PlayerComponent {
-    /// The constructor creates a new instance of the player component.
+    /// The constructor creates a new instance of the player component, with the robot on the
+    /// given tile.
     pub(crate) fn new(
         gfx: &impl Has<GraphicsContext>,
+        initial_tile: &Tile,
         initial_position: (usize, usize),
         map_size: (usize, usize),
     ) -> Self {
+        let destination = Self::destination(
+            initial_tile,
+            initial_position,
+            map_size.1,
+            Orientation::North,
+        );
+
         Self {
             image: Texture::Player.get_image(gfx),
             position: initial_position,
+            tile: initial_tile.clone(),
             map_size,
+            orientation: Orientation::North,
+            from: destination,
+            to: destination,
+            elapsed: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    /// The function returns the position of the image of the player standing on the given tile,
+    /// in world coordinates. The image is lower on half tiles.
+    fn destination(
+        tile: &Tile,
+        position: (usize, usize),
+        map_len: usize,
+        orientation: Orientation,
+    ) -> Vec2 {
+        Camera::top_face(map_len, orientation, position.0, position.1, tile) - vec2(0.0, 2.0)
+    }
+
+    /// The function moves the player to its position for the given orientation of the view,
+    /// ending the movement being animated.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.to = Self::destination(&self.tile, self.position, self.map_size.1, orientation);
+        self.from = self.to;
+    }
+
+    /// The function returns the current position of the image of the player, in world
+    /// coordinates.
+    fn current(&self) -> Vec2 {
+        if self.elapsed >= self.duration {
+            self.to
+        } else {
+            let t = self.elapsed / self.duration;
+            self.from.lerp(self.to, t * t * (3.0 - 2.0 * t))
         }
     }
+
+    /// The function returns the position (row, column) of the player.
+    pub(crate) fn position(&self) -> (usize, usize) {
+        self.position
+    }
 }
 
 impl Component<PlayerComponentParam, PlayerComponentUpdateParam> for PlayerComponent {
@@ -46,41 +120,56 @@ impl Component<PlayerComponentParam, PlayerComponentUpdateParam> for PlayerCompo
         draw_param: ggez::graphics::DrawParam,
         component_param: PlayerComponentParam,
     ) -> Result<(), ggez::GameError> {
-        let y = self.position.0 as usize; // row
-        let x = self.position.1 as usize; // column
-        let scale = component_param.scale;
-
-        let player_x =
-            (self.image.width() as f32 * 0.5) * (self.map_size.1 as usize - y + x - 1) as f32;
-        let player_y = ((Texture::height() - 1.0) * 0.25) * (x + y) as f32;
+        let scale = component_param.camera.scale();
 
         // Draws the player component.
         canvas.draw(
             &self.image,
             draw_param
-                .dest(vec2(player_x, player_y - 2.0) * scale)
-                .scale(vec2(scale, scale)),
+                .dest(self.current() * scale)
+                .scale(vec2(scale, scale))
+                .color(component_param.tint),
         );
         Ok(())
     }
 
     fn update(&mut self, update_param: PlayerComponentUpdateParam) -> Result<(), ggez::GameError> {
-        // Updates the position of the player component.
-        self.position = update_param.position;
+        match update_param._type {
+            PlayerComponentUpdateType::Moved(tile, position, duration) => {
+                let destination =
+                    Self::destination(&tile, position, self.map_size.1, self.orientation);
+
+                // A jump through a teleport is animated by the teleports component.
+                self.from = if teleported(self.position, position, &tile) {
+                    destination
+                } else {
+                    self.current()
+                };
+                self.to = destination;
+                self.elapsed = 0.0;
+                self.duration = duration;
+                self.position = position;
+                self.tile = tile;
+            }
+            PlayerComponentUpdateType::Tick(delta) => {
+                self.elapsed += delta;
+            }
+        }
+
         Ok(())
     }
 }
 
 impl PlayerComponentParam {
     /// The constructor creates a new instance of the player component parameters.
-    pub(crate) fn new(scale: f32) -> Self {
-        Self { scale }
+    pub(crate) fn new(camera: Camera, tint: Color) -> Self {
+        Self { camera, tint }
     }
 }
 
 impl PlayerComponentUpdateParam {
     /// The constructor creates a new instance of the player component update parameters.
-    pub(crate) fn new(position: (usize, usize)) -> Self {
-        Self { position }
+    pub(crate) fn new(_type: PlayerComponentUpdateType) -> Self {
+        Self { _type }
     }
 }
@@ -0,0 +1,331 @@
+use std::collections::{HashMap, VecDeque};
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{
+        Canvas, Color, DrawMode, DrawParam, FillOptions, GraphicsContext, Mesh, MeshBuilder, Quad,
+        Rect, StrokeOptions, Text, TextFragment,
+    },
+};
+use robotics_lib::world::tile::Content;
+
+use crate::visualizer::layout::{Layout, Panel};
+
+use super::Component;
+
+/// Width of the score panel.
+const PANEL_WIDTH: f32 = 400.0;
+
+/// Height of the score panel.
+const PANEL_HEIGHT: f32 = 130.0;
+
+/// Maximum number of samples kept in the score history.
+const HISTORY_LENGTH: usize = 120;
+
+/// The score component implements Component and displays the running score of the robot
+/// against the maximum score of the world, with a sparkline of its history.
+/// The score grows by the value of the content in the score table every time the robot puts
+/// contents from its backpack into a container (a bin, a crate, a bank or a market): the
+/// removal from the backpack is followed by the update of the tile receiving the contents.
+/// Contents dropped anywhere else are lost, while the ones collected into the backpack are
+/// shown as carried until the robot delivers them.
+pub(in crate::visualizer) struct ScoreComponent {
+    mesh: Mesh,
+    text: Text,
+    score_table: Option<HashMap<Content, f32>>,
+    max_score: f32,
+    deliveries: Deliveries,
+    history: VecDeque<f32>,
+}
+
+/// The struct keeps the points of the contents delivered by the robot and of the ones carried in
+/// its backpack. A removal from the backpack waits for the update of the tile receiving the
+/// contents, which follows it in the same tick.
+#[derive(Default, Debug, PartialEq)]
+struct Deliveries {
+    score: f32,
+    carried: f32,
+    removed: Option<f32>,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct ScoreComponentParam {
+    layout: Layout,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum ScoreComponentUpdateType {
+    /// Some contents have been added to the backpack.
+    AddedToBackpack(Content, usize),
+    /// Some contents have been removed from the backpack.
+    RemovedFromBackpack(Content, usize),
+    /// The content of a tile has changed.
+    TileContentUpdated(Content),
+    /// A tick has passed, so the current score is sampled into the history.
+    Tick,
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct ScoreComponentUpdateParam {
+    _type: ScoreComponentUpdateType,
+}
+
+impl ScoreComponent {
+    /// The constructor creates a new instance of the score component from the maximum score and
+    /// the score table returned by the world generator.
+    /// Without a score table, every content is worth one point.
+    pub(crate) fn new(
+        gfx: &impl Has<GraphicsContext>,
+        max_score: f32,
+        score_table: Option<HashMap<Content, f32>>,
+    ) -> Self {
+        // Builds background mesh
+        let mut mesh_builder = MeshBuilder::new();
+        mesh_builder
+            .rectangle(
+                DrawMode::Fill(FillOptions::default()),
+                Rect::new(0.0, 0.0, PANEL_WIDTH, PANEL_HEIGHT),
+                Color::from_rgba_u32(0x000080AA), // It has a bit of transparency
+            )
+            .unwrap();
+        mesh_builder
+            .rectangle(
+                DrawMode::Stroke(StrokeOptions::default().with_line_width(5.0)),
+                Rect::new(0.0, 0.0, PANEL_WIDTH, PANEL_HEIGHT),
+                Color::from_rgba_u32(0x000051FF),
+            )
+            .unwrap();
+
+        // Creates the mesh
+        let mesh = Mesh::from_data(gfx, mesh_builder.build());
+
+        // The font has been loaded previously in the main file
+        let mut text = Text::new(
+            TextFragment::new(String::new())
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(28.0),
+        );
+
+        // Sets the text bounds and wrap
+        text.set_bounds(vec2(PANEL_WIDTH - 40.0, 70.0));
+        text.set_wrap(true);
+
+        let mut score = Self {
+            mesh,
+            text,
+            score_table,
+            max_score,
+            deliveries: Deliveries::default(),
+            history: VecDeque::from([0.0]),
+        };
+
+        score.update_text();
+        score
+    }
+
+    /// The function returns the size of the panel.
+    pub(crate) fn size(&self) -> Vec2 {
+        vec2(PANEL_WIDTH, PANEL_HEIGHT)
+    }
+
+    /// The function returns the points of a single unit of the given content.
+    fn points(&self, content: &Content) -> f32 {
+        match &self.score_table {
+            // Looks up the content by kind, ignoring the quantity it carries.
+            Some(score_table) => score_table
+                .iter()
+                .find(|(key, _)| std::mem::discriminant(*key) == std::mem::discriminant(content))
+                .map(|(_, points)| *points)
+                .unwrap_or(0.0),
+            None => 1.0,
+        }
+    }
+
+    /// The function rewrites the text of the panel from the current score.
+    fn update_text(&mut self) {
+        let percentage = if self.max_score > 0.0 {
+            self.deliveries.score / self.max_score * 100.0
+        } else {
+            0.0
+        };
+
+        self.text.fragments_mut().get_mut(0).unwrap().text = format!(
+            "Score {:.1} / {:.1} ({:.1}%)\nCarried {:.1}",
+            self.deliveries.score, self.max_score, percentage, self.deliveries.carried
+        );
+    }
+}
+
+impl Deliveries {
+    /// The function adds the given points to the ones carried in the backpack.
+    fn add(&mut self, points: f32) {
+        self.carried += points;
+    }
+
+    /// The function removes the given points from the backpack. They're scored once the tile
+    /// receiving them is known.
+    fn remove(&mut self, points: f32) {
+        self.carried = (self.carried - points).max(0.0);
+        self.removed = Some(points);
+    }
+
+    /// The function scores the points of the last removal if the tile receiving them now holds
+    /// the given container content. Contents dropped anywhere else are lost.
+    fn receive(&mut self, content: &Content) {
+        if let Some(points) = self.removed.take() {
+            if matches!(
+                content,
+                Content::Bin(_) | Content::Crate(_) | Content::Bank(_) | Content::Market(_)
+            ) {
+                self.score += points;
+            }
+        }
+    }
+
+    /// The function drops the removal which no tile has received during the tick.
+    fn tick(&mut self) {
+        self.removed = None;
+    }
+}
+
+impl Component<ScoreComponentParam, ScoreComponentUpdateParam> for ScoreComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: ScoreComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let Some(position) = component_param.layout.position(Panel::Score) else {
+            return Ok(());
+        };
+
+        // Draws background mesh
+        canvas.draw(&self.mesh, draw_param.dest(position));
+
+        // Draws score text
+        canvas.draw(&self.text, draw_param.dest(position + vec2(20.0, 10.0)));
+
+        // Draws the sparkline as a sequence of bars, scaled on the highest score of the history.
+        let sparkline = Rect::new(
+            position.x + 20.0,
+            position.y + 80.0,
+            PANEL_WIDTH - 40.0,
+            40.0,
+        );
+        let highest = self
+            .history
+            .iter()
+            .fold(f32::EPSILON, |highest, score| highest.max(*score));
+        let bar_width = sparkline.w / HISTORY_LENGTH as f32;
+
+        for (i, score) in self.history.iter().enumerate() {
+            let height = (score / highest * sparkline.h).max(1.0);
+
+            canvas.draw(
+                &Quad,
+                draw_param
+                    .dest_rect(Rect::new(
+                        sparkline.x + i as f32 * bar_width,
+                        sparkline.y + sparkline.h - height,
+                        bar_width.max(1.0),
+                        height,
+                    ))
+                    .color(Color::from_rgb_u32(0xFFD700)),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, update_param: ScoreComponentUpdateParam) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            ScoreComponentUpdateType::AddedToBackpack(content, count) => {
+                self.deliveries.add(self.points(&content) * count as f32);
+                self.update_text();
+            }
+            ScoreComponentUpdateType::RemovedFromBackpack(content, count) => {
+                self.deliveries.remove(self.points(&content) * count as f32);
+                self.update_text();
+            }
+            ScoreComponentUpdateType::TileContentUpdated(content) => {
+                self.deliveries.receive(&content);
+                self.update_text();
+            }
+            ScoreComponentUpdateType::Tick => {
+                self.deliveries.tick();
+                self.history.push_back(self.deliveries.score);
+
+                if self.history.len() > HISTORY_LENGTH {
+                    self.history.pop_front();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ScoreComponentParam {
+    /// The constructor creates a new instance of the score component parameters.
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self { layout }
+    }
+}
+
+impl ScoreComponentUpdateParam {
+    /// The constructor creates a new instance of the score component update parameters.
+    pub(crate) fn new(_type: ScoreComponentUpdateType) -> Self {
+        Self { _type }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contents_put_into_a_bank_are_scored() {
+        let mut deliveries = Deliveries::default();
+        deliveries.add(5.0);
+        deliveries.remove(5.0);
+        deliveries.receive(&Content::Bank(0..10));
+
+        assert_eq!(deliveries.score, 5.0);
+        assert_eq!(deliveries.carried, 0.0);
+    }
+
+    #[test]
+    fn contents_dropped_on_the_ground_are_lost() {
+        let mut deliveries = Deliveries::default();
+        deliveries.add(3.0);
+        deliveries.remove(3.0);
+        deliveries.receive(&Content::Rock(1));
+
+        // A later container update doesn't score the lost contents.
+        deliveries.receive(&Content::Bin(0..10));
+
+        assert_eq!(deliveries.score, 0.0);
+        assert_eq!(deliveries.carried, 0.0);
+    }
+
+    #[test]
+    fn removals_without_an_update_are_dropped_on_tick() {
+        let mut deliveries = Deliveries::default();
+        deliveries.add(4.0);
+        deliveries.remove(2.0);
+        deliveries.tick();
+        deliveries.receive(&Content::Crate(0..10));
+
+        assert_eq!(deliveries.score, 0.0);
+
+        // Removals don't add up across events either.
+        deliveries.remove(1.0);
+        deliveries.remove(1.0);
+        deliveries.receive(&Content::Market(1));
+
+        assert_eq!(deliveries.score, 1.0);
+        assert_eq!(deliveries.carried, 0.0);
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::mem::discriminant;
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{
+        Canvas, Color, DrawMode, DrawParam, GraphicsContext, Mesh, Quad, Rect, Text, TextFragment,
+    },
+};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::visualizer::{
+    camera::Camera,
+    layout::{Layout, Panel},
+};
+
+use super::inspector::{content_description, tile_type_name};
+use super::Component;
+
+/// Width of the side panel.
+const PANEL_WIDTH: f32 = 420.0;
+
+/// Height of a line of the panel text.
+const LINE_HEIGHT: f32 = 24.0;
+
+/// Maximum number of changes kept in the history of a tile.
+const HISTORY_LENGTH: usize = 8;
+
+/// The selection component implements Component. It outlines the tile pinned as the selected
+/// one and shows a side panel with its details and its change history, drawn on the
+/// screen-space pass.
+/// The history of every tile is recorded, so it's already available when a tile gets selected.
+/// It's built from the tiles discovered by the robot and from the changes found by the tiles
+/// map, which keeps the previous version of every changed tile.
+pub(in crate::visualizer) struct SelectionComponent {
+    outline: Mesh,
+    text: Text,
+    map_len: usize,
+    selected: Option<((usize, usize), Tile, bool)>,
+    history: HashMap<(usize, usize), VecDeque<(usize, Change)>>,
+    ticks: usize,
+}
+
+/// The changes recorded in the history of a tile. They're described only when the tile is shown.
+enum Change {
+    Discovered,
+    Entered,
+    Type(TileType, TileType),
+    Elevation(usize, usize),
+    Content(Content, Content),
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct SelectionComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum SelectionComponentUpdateType<'a> {
+    /// The tile in the given coordinates has been selected, with whether it's known by the robot.
+    Selected((usize, usize), Tile, bool),
+    /// The selection has been cleared.
+    Cleared,
+    /// The robot has moved into the given position.
+    Moved((usize, usize)),
+    /// The robot has discovered the tiles in the given coordinates, while the other given tiles
+    /// have changed from their previous version into the one of the given known world.
+    Changed(
+        &'a [(usize, usize)],
+        &'a [((usize, usize), Tile)],
+        &'a [Vec<Option<Tile>>],
+    ),
+    /// A tick has passed.
+    Tick,
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct SelectionComponentUpdateParam<'a> {
+    _type: SelectionComponentUpdateType<'a>,
+}
+
+impl SelectionComponent {
+    /// The constructor creates a new instance of the selection component for a map of the given
+    /// size, without a selected tile.
+    pub(crate) fn new(gfx: &impl Has<GraphicsContext>, map_len: usize) -> Self {
+        // The outline follows the top face of a block, relative to the top left corner of its
+        // image.
+        let center = Camera::face_center();
+        let outline = Mesh::new_polygon(
+            gfx,
+            DrawMode::stroke(0.75),
+            &[
+                vec2(center.x, 1.0),
+                vec2(center.x * 2.0, center.y),
+                vec2(center.x, center.y * 2.0 - 1.0),
+                vec2(0.0, center.y),
+            ],
+            Color::CYAN,
+        )
+        .unwrap();
+
+        // The font has been loaded previously in the main file
+        let mut text = Text::new(
+            TextFragment::new(String::new())
+                .font("kode")
+                .color(Color::WHITE)
+                .scale(20.0),
+        );
+
+        text.set_bounds(vec2(PANEL_WIDTH - 20.0, f32::INFINITY));
+        text.set_wrap(true);
+
+        Self {
+            outline,
+            text,
+            map_len,
+            selected: None,
+            history: HashMap::new(),
+            ticks: 0,
+        }
+    }
+
+    /// The function returns the coordinates of the selected tile, if any.
+    pub(crate) fn selected(&self) -> Option<(usize, usize)> {
+        self.selected.as_ref().map(|(coords, _, _)| *coords)
+    }
+
+    /// The function adds a change to the history of the tile in the given coordinates,
+    /// forgetting the oldest one when the history is full.
+    fn record(&mut self, coords: (usize, usize), change: Change) {
+        let history = self.history.entry(coords).or_default();
+
+        history.push_back((self.ticks, change));
+        if history.len() > HISTORY_LENGTH {
+            history.pop_front();
+        }
+    }
+
+    /// The function returns the differences between the previously known tile and the given one.
+    fn changes(previous: &Tile, tile: &Tile) -> Vec<Change> {
+        let mut changes = Vec::new();
+
+        // Teleports are also compared on whether they're active.
+        let same_type = match (&previous.tile_type, &tile.tile_type) {
+            (TileType::Teleport(previous), TileType::Teleport(current)) => previous == current,
+            (previous, current) => discriminant(previous) == discriminant(current),
+        };
+        if !same_type {
+            changes.push(Change::Type(previous.tile_type, tile.tile_type));
+        }
+
+        if previous.elevation != tile.elevation {
+            changes.push(Change::Elevation(previous.elevation, tile.elevation));
+        }
+
+        if previous.content != tile.content {
+            changes.push(Change::Content(
+                previous.content.clone(),
+                tile.content.clone(),
+            ));
+        }
+
+        changes
+    }
+
+    /// The function returns the size of the panel with the details of the selected tile, if a
+    /// tile is selected.
+    pub(crate) fn panel_size(&self) -> Option<Vec2> {
+        self.selected.as_ref().map(|_| {
+            let lines = self.text.fragments()[0].text.lines().count() as f32;
+            vec2(PANEL_WIDTH, lines * LINE_HEIGHT + 20.0)
+        })
+    }
+
+    /// The function draws the panel with the details of the selected tile where the given
+    /// layout has placed it.
+    pub(crate) fn draw_panel(&self, canvas: &mut Canvas, draw_param: DrawParam, layout: &Layout) {
+        let (Some(panel), Some(size)) = (layout.position(Panel::Selection), self.panel_size())
+        else {
+            return;
+        };
+
+        canvas.draw(
+            &Quad,
+            draw_param
+                .dest_rect(Rect::new(panel.x, panel.y, size.x, size.y))
+                .color(Color::from_rgba_u32(0x000080DD)),
+        );
+
+        canvas.draw(&self.text, draw_param.dest(panel + vec2(10.0, 10.0)));
+    }
+
+    /// The function rewrites the text of the panel from the selected tile.
+    fn update_text(&mut self) {
+        let Some((coords, tile, discovered)) = &self.selected else {
+            return;
+        };
+
+        let mut text = format!(
+            "Selected tile {:?}\nType: {}\nElevation: {}\nContent: {}\nDiscovered: {}\n\nHistory:",
+            coords,
+            tile_type_name(&tile.tile_type),
+            tile.elevation,
+            content_description(&tile.content),
+            if *discovered { "yes" } else { "no" }
+        );
+
+        match self.history.get(coords) {
+            // The most recent changes are listed first.
+            Some(history) => history.iter().rev().for_each(|(tick, change)| {
+                text.push_str(&format!("\n  Tick {}: {}", tick, change.description()));
+            }),
+            None => text.push_str("\n  No changes yet"),
+        }
+
+        self.text.fragments_mut().get_mut(0).unwrap().text = text;
+    }
+}
+
+impl<'a> Component<SelectionComponentParam, SelectionComponentUpdateParam<'a>>
+    for SelectionComponent
+{
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: SelectionComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let Some((coords, tile, _)) = &self.selected else {
+            return Ok(());
+        };
+
+        let scale = component_param.camera.scale();
+
+        // Draws the outline over the top face of the selected tile.
+        let position = Camera::top_face(
+            self.map_len,
+            component_param.camera.orientation(),
+            coords.0,
+            coords.1,
+            tile,
+        );
+
+        canvas.draw(
+            &self.outline,
+            draw_param.dest(position * scale).scale(vec2(scale, scale)),
+        );
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: SelectionComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            SelectionComponentUpdateType::Selected(coords, tile, discovered) => {
+                self.selected = Some((coords, tile, discovered));
+            }
+            SelectionComponentUpdateType::Cleared => {
+                self.selected = None;
+            }
+            SelectionComponentUpdateType::Moved(coords) => {
+                self.record(coords, Change::Entered);
+            }
+            SelectionComponentUpdateType::Changed(discovered, changes, world) => {
+                for coords in discovered {
+                    self.record(*coords, Change::Discovered);
+                }
+
+                // A tile just discovered wasn't known before, so it has no previous version.
+                let discovered = discovered.iter().collect::<HashSet<_>>();
+                for (coords, previous) in changes {
+                    if discovered.contains(coords) {
+                        continue;
+                    }
+
+                    if let Some(tile) = &world[coords.0][coords.1] {
+                        for change in Self::changes(previous, tile) {
+                            self.record(*coords, change);
+                        }
+                    }
+                }
+
+                // Keeps the details of the selected tile in sync with the robot's knowledge.
+                if let Some((coords, tile, known)) = &mut self.selected {
+                    if let Some(current) = &world[coords.0][coords.1] {
+                        *tile = current.clone();
+                        *known = true;
+                    }
+                }
+            }
+            SelectionComponentUpdateType::Tick => {
+                self.ticks += 1;
+            }
+        }
+
+        self.update_text();
+
+        Ok(())
+    }
+}
+
+impl Change {
+    /// The function returns the description of the change shown in the history.
+    fn description(&self) -> String {
+        match self {
+            Change::Discovered => String::from("Discovered"),
+            Change::Entered => String::from("Entered by the robot"),
+            Change::Type(previous, current) => format!(
+                "Type {} -> {}",
+                tile_type_name(previous),
+                tile_type_name(current)
+            ),
+            Change::Elevation(previous, current) => {
+                format!("Elevation {} -> {}", previous, current)
+            }
+            Change::Content(previous, current) => format!(
+                "Content {} -> {}",
+                content_description(previous),
+                content_description(current)
+            ),
+        }
+    }
+}
+
+impl SelectionComponentParam {
+    /// The constructor creates a new instance of the selection component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl<'a> SelectionComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the selection component update parameters.
+    pub(crate) fn new(_type: SelectionComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
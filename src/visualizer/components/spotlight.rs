@@ -0,0 +1,205 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ggez::{
+    context::Has,
+    glam::vec2,
+    graphics::{Canvas, Color, DrawParam, GraphicsContext, InstanceArray},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    textures::Texture,
+};
+
+use super::Component;
+
+/// The spotlight component implements Component and previews the area a spotlight call of the
+/// given distance would reveal around the robot. It also highlights the tiles discovered during
+/// the last tick.
+pub(in crate::visualizer) struct SpotlightComponent {
+    map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    area: InstanceArray,
+    discovered: InstanceArray,
+    last_discovered: Vec<(usize, usize)>,
+    position: (usize, usize),
+    distance: usize,
+    orientation: Orientation,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct SpotlightComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum SpotlightComponentUpdateType<'a> {
+    /// The robot has moved into the given position.
+    Moved((usize, usize)),
+    /// A tick has passed, discovering the given tiles (row, column).
+    Discovered(&'a [(usize, usize)]),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct SpotlightComponentUpdateParam<'a> {
+    _type: SpotlightComponentUpdateType<'a>,
+}
+
+impl SpotlightComponent {
+    /// The constructor creates a new instance of the spotlight component from the shared
+    /// reference to the map, with the robot in the given position and the given distance of the
+    /// spotlight. The preview is hidden by default.
+    pub(crate) fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        initial_position: (usize, usize),
+        distance: usize,
+    ) -> Self {
+        let mut spotlight = Self {
+            map_rc,
+            area: InstanceArray::new(gfx, Texture::Highlight.get_image(gfx)),
+            discovered: InstanceArray::new(gfx, Texture::Highlight.get_image(gfx)),
+            last_discovered: Vec::new(),
+            position: initial_position,
+            distance,
+            orientation: Orientation::North,
+            enabled: false,
+        };
+
+        spotlight.update_area();
+        spotlight
+    }
+
+    /// The function shows or hides the preview and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function sets the distance of the previewed spotlight.
+    pub(crate) fn set_distance(&mut self, distance: usize) {
+        self.distance = distance;
+        self.update_area();
+    }
+
+    /// The function returns the distance of the previewed spotlight.
+    pub(crate) fn distance(&self) -> usize {
+        self.distance
+    }
+
+    /// The function moves the highlights to their position for the given orientation of the
+    /// view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.update_area();
+        self.update_discovered();
+    }
+
+    /// The function returns the draw param of the highlight of the given tile.
+    fn highlight(map: &[Vec<Tile>], orientation: Orientation, row: usize, col: usize) -> DrawParam {
+        DrawParam::new().dest(Camera::top_face(
+            map.len(),
+            orientation,
+            row,
+            col,
+            &map[row][col],
+        ))
+    }
+
+    /// The function places the highlight on the tiles discovered during the last tick.
+    fn update_discovered(&mut self) {
+        let map = self.map_rc.borrow();
+
+        self.discovered.set(
+            self.last_discovered
+                .iter()
+                .map(|(row, col)| Self::highlight(&map, self.orientation, *row, *col)),
+        );
+    }
+
+    /// The function places the highlight on the tiles within the distance of the spotlight from
+    /// the robot.
+    fn update_area(&mut self) {
+        let map = self.map_rc.borrow();
+        let (row, col) = self.position;
+        let distance = self.distance;
+        let orientation = self.orientation;
+
+        let rows = row.saturating_sub(distance)..=(row + distance).min(map.len() - 1);
+        let cols = col.saturating_sub(distance)..=(col + distance).min(map.len() - 1);
+
+        self.area.set(
+            rows.flat_map(|r| cols.clone().map(move |c| (r, c)))
+                .filter(|(r, c)| r.abs_diff(row).pow(2) + c.abs_diff(col).pow(2) <= distance.pow(2))
+                .map(|(r, c)| Self::highlight(&map, orientation, r, c)),
+        );
+    }
+}
+
+impl<'a> Component<SpotlightComponentParam, SpotlightComponentUpdateParam<'a>>
+    for SpotlightComponent
+{
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: SpotlightComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let scale = vec2(
+            component_param.camera.scale(),
+            component_param.camera.scale(),
+        );
+
+        canvas.draw(
+            &self.area,
+            draw_param
+                .scale(scale)
+                .color(Color::new(1.0, 1.0, 0.6, 0.25)),
+        );
+        canvas.draw(
+            &self.discovered,
+            draw_param
+                .scale(scale)
+                .color(Color::new(0.0, 1.0, 0.5, 0.6)),
+        );
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: SpotlightComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            SpotlightComponentUpdateType::Moved(position) => {
+                self.position = position;
+                self.update_area();
+            }
+            SpotlightComponentUpdateType::Discovered(discovered) => {
+                self.last_discovered = discovered.to_vec();
+                self.update_discovered();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SpotlightComponentParam {
+    /// The constructor creates a new instance of the spotlight component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl<'a> SpotlightComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the spotlight component update parameters.
+    pub(crate) fn new(_type: SpotlightComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
@@ -1,70 +1,109 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use ggez::{
     context::Has,
     glam::{vec2, Vec2},
-    graphics::{Canvas, Color, DrawParam, GraphicsContext, InstanceArray},
+    graphics::{Canvas, Color, DrawParam, GraphicsContext},
     GameResult,
 };
 
 use robotics_lib::world::tile::Tile;
 
-use crate::visualizer::textures::Texture;
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    fog::Fog,
+    textures::Texture,
+};
 
 use super::{Component, CoordinatedInstance};
 
 /// State of the tiles map component.
 pub(in crate::visualizer) struct TilesMapComponent {
     map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    fog_rc: Rc<RefCell<Fog>>,
     instances: Vec<HashMap<Texture, CoordinatedInstance>>,
+    hidden: HashSet<Texture>,
+    orientation: Orientation,
+    changes: Vec<((usize, usize), Tile)>,
 }
 
 /// Draw parameters for the tiles map component.
 pub(in crate::visualizer) struct TilesMapComponentParam {
-    pub origin: Vec2,
+    pub camera: Camera,
     pub window_size: Vec2,
-    pub scale: f32,
+    pub tint: Color,
 }
 
 /// Update parameters for the tiles map component.
-pub(in crate::visualizer) struct TilesMapComponentUpdateParam {
-    pub current_map: Vec<Vec<Option<Tile>>>,
+pub(in crate::visualizer) struct TilesMapComponentUpdateParam<'a> {
+    pub current_map: &'a [Vec<Option<Tile>>],
 }
 
 /// TilesMapComponent draws a map in isometric perspective.
-/// Isometric must be drawn in diagonal order, from the top left to the bottom right, in order to
-/// correctly draw the tiles avoiding overlapping issues.
+/// Isometric must be drawn in diagonal order, from the back to the front of the view, in order to
+/// correctly draw the tiles avoiding overlapping issues. The diagonals depend on the orientation
+/// of the view, so they're rebuilt when it's rotated.
 impl TilesMapComponent {
-    /// Create a new instance of the TilesMapComponent from the given map.
-    pub fn from_map(gfx: &impl Has<GraphicsContext>, map_rc: Rc<RefCell<Vec<Vec<Tile>>>>) -> Self {
-        let mut diagonals: Vec<Vec<(usize, usize)>> = Vec::new();
-        let mut instances: Vec<HashMap<Texture, CoordinatedInstance>> = Vec::new();
-        let map = map_rc.borrow().clone();
-
-        // Create a list of diagonals, from the top left to the bottom right.
-        for k in 0..=(2 * map.len() - 2) as usize {
-            let mut diagonal_components = Vec::new();
-            for x in 0..=k {
-                let y = k - x;
-                if y < map.len() && x < map.len() {
-                    diagonal_components.push((x, y));
-                }
-            }
-            if !diagonal_components.is_empty() {
-                diagonals.push(diagonal_components);
+    /// Create a new instance of the TilesMapComponent from the given map, colored by the given
+    /// fog of war. The view faces north at first.
+    pub fn from_map(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog_rc: Rc<RefCell<Fog>>,
+    ) -> Self {
+        let instances =
+            Self::create_instances(gfx, map_rc.clone(), &fog_rc.borrow(), Orientation::North);
+
+        Self {
+            map_rc,
+            fog_rc,
+            instances,
+            hidden: HashSet::new(),
+            orientation: Orientation::North,
+            changes: Vec::new(),
+        }
+    }
+
+    /// The function rebuilds the diagonals of the map for the given orientation of the view.
+    pub(in crate::visualizer) fn set_orientation(
+        &mut self,
+        gfx: &impl Has<GraphicsContext>,
+        orientation: Orientation,
+    ) {
+        self.orientation = orientation;
+        self.instances =
+            Self::create_instances(gfx, self.map_rc.clone(), &self.fog_rc.borrow(), orientation);
+    }
+
+    /// This private method creates the instances of every diagonal of the map, from the back to
+    /// the front of the view.
+    fn create_instances(
+        gfx: &impl Has<GraphicsContext>,
+        map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog: &Fog,
+        orientation: Orientation,
+    ) -> Vec<HashMap<Texture, CoordinatedInstance>> {
+        let map_len = map_rc.borrow().len();
+        let mut diagonals: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 2 * map_len - 1];
+
+        // Create a list of diagonals, from the back to the front of the view.
+        for y in 0..map_len {
+            for x in 0..map_len {
+                diagonals[orientation.diagonal(map_len, y, x)].push((x, y));
             }
         }
 
         // For each diagonal, create a new instance of the TilesMapComponent.
-        diagonals.iter().for_each(|diagonal| {
-            instances.push(Self::create_diagonal_instances(
-                gfx,
-                map_rc.clone(),
-                diagonal,
-            ));
-        });
-
-        Self { map_rc, instances }
+        diagonals
+            .iter()
+            .map(|diagonal| {
+                Self::create_diagonal_instances(gfx, map_rc.clone(), fog, diagonal, orientation)
+            })
+            .collect()
     }
 
     /// This private method called by the constructor, creates a new instance of a diagonal row
@@ -72,7 +111,9 @@ impl TilesMapComponent {
     fn create_diagonal_instances(
         gfx: &impl Has<GraphicsContext>,
         map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+        fog: &Fog,
         diagonal: &Vec<(usize, usize)>,
+        orientation: Orientation,
     ) -> HashMap<Texture, CoordinatedInstance> {
         let map = map_rc.borrow();
 
@@ -82,10 +123,7 @@ impl TilesMapComponent {
             .map(|texture| {
                 (
                     *texture,
-                    CoordinatedInstance {
-                        array: InstanceArray::new(gfx, texture.get_image(gfx)),
-                        elements: Vec::new(),
-                    },
+                    CoordinatedInstance::new(gfx, texture.get_image(gfx)),
                 )
             })
             .collect::<HashMap<_, _>>();
@@ -94,50 +132,103 @@ impl TilesMapComponent {
         diagonal.iter().for_each(|(x, y)| {
             let texture = Texture::from_tile(&map[*y][*x]);
 
-            // Image positions are calculated using the isometric perspective.
-            let image_x = (Texture::width() * 0.5) * (map.len() - y + x - 1) as f32;
-            let image_y = ((Texture::height() - 1.0) * 0.25) * (x + y) as f32;
-
             let instance = diagonal_instances.get_mut(&texture).unwrap();
 
-            // Color the tile with a black transparent color.
-            // The functions hides the tile by default. It will be shown when discovered by the
-            // robot.
-            instance.array.push(
+            // Color the tile by the fog of war.
+            // The tile is hidden by default. It will be shown when discovered by the robot.
+            // The draw param is traced by the coordinates of the tile in the map.
+            instance.push(
+                (*x, *y),
                 ggez::graphics::DrawParam::new()
-                    .dest(Vec2::new(image_x, image_y))
-                    .color(Color::from_rgba(0, 0, 0, 127)),
+                    .dest(Camera::tile_to_world(
+                        map.len(),
+                        orientation,
+                        *y as f32,
+                        *x as f32,
+                    ))
+                    .color(fog.color(*y, *x)),
             );
-
-            // Push into the elements the coordinates of the tile in the map.
-            instance.elements.push((*x, *y));
         });
 
         diagonal_instances
     }
+
+    /// The function sets the textures which are not drawn.
+    pub(in crate::visualizer) fn set_hidden(&mut self, hidden: HashSet<Texture>) {
+        self.hidden = hidden;
+    }
+
+    /// The function colors every tile by the current state of the fog of war.
+    pub(in crate::visualizer) fn refresh_fog(&mut self) {
+        let fog = self.fog_rc.borrow();
+
+        for diagonal_instances in self.instances.iter_mut() {
+            for instance in diagonal_instances.values_mut() {
+                instance.update_all(|(x, y), draw_param| draw_param.color(fog.color(y, x)));
+            }
+        }
+    }
+
+    /// The function colors the tiles in the given coordinates (row, column) by the current state
+    /// of the fog of war.
+    pub(in crate::visualizer) fn refresh_fog_at(&mut self, coords: &[(usize, usize)]) {
+        let fog = self.fog_rc.borrow();
+        let map = self.map_rc.borrow();
+        let map_len = map.len();
+
+        for (y, x) in coords.iter().copied() {
+            let diagonal = self.orientation.diagonal(map_len, y, x);
+
+            if let Some(instance) =
+                self.instances[diagonal].get_mut(&Texture::from_tile(&map[y][x]))
+            {
+                instance.update((x, y), |draw_param| draw_param.color(fog.color(y, x)));
+            }
+        }
+    }
+
+    /// The function returns the coordinates (row, column) and the previous version of the tiles
+    /// changed by the last update.
+    pub(in crate::visualizer) fn changes(&self) -> &[((usize, usize), Tile)] {
+        &self.changes
+    }
 }
 
-impl Component<TilesMapComponentParam, TilesMapComponentUpdateParam> for TilesMapComponent {
+impl<'a> Component<TilesMapComponentParam, TilesMapComponentUpdateParam<'a>> for TilesMapComponent {
     fn draw(
         &self,
         canvas: &mut Canvas,
         _draw_param: DrawParam,
         component_param: TilesMapComponentParam,
     ) -> Result<(), ggez::GameError> {
-        //  Draws the tiles in the correct order, from the top left to the bottom right.
+        let camera = component_param.camera;
+        let map_len = self.map_rc.borrow().len();
+
+        //  Draws the tiles in the correct order, from the back to the front of the view.
         self.instances.iter().enumerate().for_each(|(y, row)| {
-            row.iter().for_each(|(_, instance)| {
+            row.iter().for_each(|(texture, instance)| {
                 // Check if current diagonal is actually visible. If not, skip the drawing.
                 // This is necessary to avoid lagging issues.
-                let row_position =
-                    3.75 * component_param.scale * y as f32 - component_param.origin.y;
-                if instance.elements.capacity() > 0
-                    && row_position + 16.0 * component_param.scale >= 0.0
-                    && row_position < component_param.window_size.y as f32
+                // The diagonal is already in the coordinates of the rotated map, so its first
+                // tile is projected as if facing north.
+                let row_position = camera
+                    .world_to_screen(Camera::tile_to_world(
+                        map_len,
+                        Orientation::North,
+                        y as f32,
+                        0.0,
+                    ))
+                    .y;
+                if !instance.is_empty()
+                    && !self.hidden.contains(texture)
+                    && row_position + Texture::height() * camera.scale() >= 0.0
+                    && row_position < component_param.window_size.y
                 {
                     canvas.draw(
-                        &instance.array,
-                        DrawParam::new().scale(vec2(component_param.scale, component_param.scale)),
+                        instance.array(),
+                        DrawParam::new()
+                            .scale(vec2(camera.scale(), camera.scale()))
+                            .color(component_param.tint),
                     );
                 }
             });
@@ -145,132 +236,43 @@ impl Component<TilesMapComponentParam, TilesMapComponentUpdateParam> for TilesMa
         Ok(())
     }
 
-    fn update(&mut self, update_param: TilesMapComponentUpdateParam) -> GameResult {
-        // Current map contains the tiles discovered by the robot.
-        let current_map = update_param.current_map();
-
-        // Map contains the map discovered until the previous tick.
-        let map = self.map_rc.clone().borrow().clone();
-
-        // Updated map clones the previous map and will be updated with the new discovered tiles.
-        // It will replace the old one.
-        let mut updated_map = map.clone();
-
-        // Set color white for every discovered tile
-        map.iter().enumerate().for_each(|(y, row)| {
-            row.iter().enumerate().for_each(|(x, tile)| {
-                // If tile was discovered previously by the player
-                if current_map[y][x].is_some() {
-                    // Get the instance of the tile
-                    let diagonal_instance = &mut self.instances[x + y];
-
-                    // Calculate the position of the tile in the matrix
-                    let image_x = (Texture::width() * 0.5) * (map.len() - y + x - 1) as f32;
-                    let image_y = ((Texture::height() - 1.0) * 0.25) * (x + y) as f32;
-
-                    // Get the position of the tile in the instance array
-                    // Suppose that the tile exists in the elements array
-                    let position = diagonal_instance
-                        .get_mut(&Texture::from_tile(tile))
-                        .unwrap()
-                        .elements
-                        .iter()
-                        .position(|(e_x, e_y)| x == *e_x && y == *e_y)
-                        .unwrap();
-
-                    // Update the corrisponding draw param
-                    diagonal_instance
-                        .get_mut(&Texture::from_tile(tile))
-                        .unwrap()
-                        .array
-                        .update(
-                            position as u32,
-                            DrawParam::new()
-                                .dest(vec2(image_x, image_y))
-                                .color(Color::WHITE),
-                        );
-                }
-            });
-        });
+    fn update(&mut self, update_param: TilesMapComponentUpdateParam<'a>) -> GameResult {
+        let map_rc = self.map_rc.clone();
+        let mut map = map_rc.borrow_mut();
+        let map_len = map.len();
 
-        // Update modified tiles.
-        map.iter()
-            // Compare elements of the previous map with the current map in pairs.
-            .zip(current_map.iter())
-            .enumerate()
-            .for_each(|(y, (prev_row, last_row))| {
-                prev_row
-                    .iter()
-                    .zip(last_row.iter())
-                    .enumerate()
-                    .filter_map(|(x, (prev_tile, last_tile))| {
-                        // Remove not discovered tiles from the list
-                        if let Some(tile) = last_tile {
-                            Some((x, (prev_tile, tile)))
-                        } else {
-                            None
-                        }
-                    })
-                    .for_each(|(x, (prev_tale, last_tale))| {
-                        // Check if tile texture has changed
-                        let prev_texture = Texture::from_tile(prev_tale);
-                        let last_texture = Texture::from_tile(last_tale);
-
-                        if prev_tale != last_tale {
-                            let diagonal_instances = &mut self.instances[x + y];
-
-                            // Get actual instance of the tile
-                            // Suppose that the tile exists in the elements array
-                            let prev_instance = diagonal_instances.get_mut(&prev_texture).unwrap();
-
-                            // Get the position of the tile in the instance array
-                            let element_position: usize = prev_instance
-                                .elements
-                                .iter()
-                                .position(|(e_x, e_y)| x == *e_x && y == *e_y)
-                                .unwrap()
-                                .clone();
-
-                            // Get corresponding draw param
-                            let draw_param = prev_instance
-                                .array
-                                .instances()
-                                .get(element_position)
-                                .unwrap()
-                                .clone();
-
-                            // Remove the draw param slicing the array
-                            prev_instance.array.set(
-                                prev_instance
-                                    .array
-                                    .instances()
-                                    .into_iter()
-                                    .enumerate()
-                                    .filter_map(|(i, draw_param)| {
-                                        if i != element_position {
-                                            Some(draw_param.clone())
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect::<Vec<_>>(),
-                            );
-
-                            // Push the draw param in the new instance of the texture
-                            diagonal_instances
-                                .get_mut(&last_texture)
-                                .unwrap()
-                                .array
-                                .push(draw_param.clone());
-
-                            // Update corrisponding tile in the map
-                            updated_map[y][x] = last_tale.clone();
-                        }
-                    })
-            });
+        self.changes.clear();
 
-        // Replace the old map with the updated one
-        self.map_rc.replace(updated_map);
+        // Compares the tiles discovered by the robot with the ones of the map, in place.
+        for (y, row) in update_param.current_map().iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let Some(tile) = tile else {
+                    continue;
+                };
+
+                if map[y][x] == *tile {
+                    continue;
+                }
+
+                // Moves the draw param of the tile to the instance of its new texture. The tile
+                // is supposed to exist in the elements of the previous one.
+                let diagonal_instances =
+                    &mut self.instances[self.orientation.diagonal(map_len, y, x)];
+                let draw_param = diagonal_instances
+                    .get_mut(&Texture::from_tile(&map[y][x]))
+                    .unwrap()
+                    .swap_remove((x, y))
+                    .unwrap();
+                diagonal_instances
+                    .get_mut(&Texture::from_tile(tile))
+                    .unwrap()
+                    .push((x, y), draw_param);
+
+                // Updates the tile in the map, keeping the previous one among the changes.
+                let previous = std::mem::replace(&mut map[y][x], tile.clone());
+                self.changes.push(((y, x), previous));
+            }
+        }
 
         Ok(())
     }
@@ -278,23 +280,23 @@ impl Component<TilesMapComponentParam, TilesMapComponentUpdateParam> for TilesMa
 
 impl TilesMapComponentParam {
     /// The constructor creates a new instance of the draw parameters.
-    pub(in crate::visualizer) fn new(origin: Vec2, window_size: Vec2, scale: f32) -> Self {
+    pub(in crate::visualizer) fn new(camera: Camera, window_size: Vec2, tint: Color) -> Self {
         Self {
-            origin,
+            camera,
             window_size,
-            scale,
+            tint,
         }
     }
 }
 
-impl TilesMapComponentUpdateParam {
+impl<'a> TilesMapComponentUpdateParam<'a> {
     /// The constructor creates a new instance of the update parameters.
-    pub(in crate::visualizer) fn new(current_map: Vec<Vec<Option<Tile>>>) -> Self {
+    pub(in crate::visualizer) fn new(current_map: &'a [Vec<Option<Tile>>]) -> Self {
         Self { current_map }
     }
 
     /// The function returns the reference to the current map.
-    pub(in crate::visualizer) fn current_map(&self) -> &Vec<Vec<Option<Tile>>> {
-        &self.current_map
+    pub(in crate::visualizer) fn current_map(&self) -> &'a [Vec<Option<Tile>>] {
+        self.current_map
     }
 }
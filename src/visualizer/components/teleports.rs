@@ -0,0 +1,340 @@
+use std::f32::consts::PI;
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, DrawParam, GraphicsContext, Image, Mesh, Quad},
+};
+use robotics_lib::world::tile::{Tile, TileType};
+
+use crate::visualizer::{
+    camera::{Camera, Orientation},
+    textures::Texture,
+};
+
+use super::{teleported, Component};
+
+/// Duration in seconds of the animation of a jump through a teleport.
+const JUMP_DURATION: f32 = 1.2;
+
+/// Thickness of the links between the teleports, in world coordinates.
+const LINK_THICKNESS: f32 = 0.75;
+
+/// A teleport known by the robot.
+struct Teleport {
+    coords: (usize, usize),
+    tile: Tile,
+    /// Center of the top face of the tile, in world coordinates.
+    position: Vec2,
+    active: bool,
+}
+
+/// A jump of the robot through a teleport, which is being animated.
+struct Jump {
+    from: (usize, usize),
+    to: (usize, usize),
+    from_position: Vec2,
+    to_position: Vec2,
+    elapsed: f32,
+}
+
+/// The teleports component implements Component and draws the network of the teleports known
+/// by the robot, marking them as active or inactive and linking all of them. Links between two
+/// active teleports stand out, while the ones reaching an inactive teleport are faded.
+/// It also animates the robot when it jumps through a teleport, instead of moving it instantly.
+pub(in crate::visualizer) struct TeleportsComponent {
+    teleports: Vec<Teleport>,
+    jump: Option<Jump>,
+    last_coords: (usize, usize),
+    last_tile: Tile,
+    last_position: Vec2,
+    map_len: usize,
+    orientation: Orientation,
+    marker: Mesh,
+    player: Image,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct TeleportsComponentParam {
+    camera: Camera,
+    tint: Color,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum TeleportsComponentUpdateType<'a> {
+    /// The tiles known by the robot have changed, so the teleports are searched again.
+    WorldVisibility(&'a [Vec<Option<Tile>>]),
+    /// The robot has moved into the given tile.
+    Moved(Tile, (usize, usize)),
+    /// The given seconds have elapsed since the previous frame.
+    Tick(f32),
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct TeleportsComponentUpdateParam<'a> {
+    _type: TeleportsComponentUpdateType<'a>,
+}
+
+impl TeleportsComponent {
+    /// The constructor creates a new instance of the teleports component, with the robot on the
+    /// given tile. The network is shown by default.
+    pub(crate) fn new(
+        gfx: &impl Has<GraphicsContext>,
+        initial_tile: &Tile,
+        initial_position: (usize, usize),
+        map_len: usize,
+    ) -> Self {
+        let marker = Mesh::new_circle(
+            gfx,
+            DrawMode::stroke(0.75),
+            vec2(0.0, 0.0),
+            5.0,
+            0.1,
+            Color::WHITE,
+        )
+        .unwrap();
+
+        let mut teleports = Self {
+            teleports: Vec::new(),
+            jump: None,
+            last_coords: initial_position,
+            last_tile: initial_tile.clone(),
+            last_position: Vec2::ZERO,
+            map_len,
+            orientation: Orientation::North,
+            marker,
+            player: Texture::Player.get_image(gfx),
+            enabled: true,
+        };
+
+        teleports.last_position = teleports.center(initial_tile, initial_position);
+        teleports
+    }
+
+    /// The function shows or hides the network of teleports and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function returns the position of the robot while it jumps through a teleport, as
+    /// fractional (column, row) coordinates, or None if it isn't jumping.
+    pub(crate) fn jump(&self) -> Option<Vec2> {
+        self.jump.as_ref().map(|jump| {
+            let from = vec2(jump.from.1 as f32, jump.from.0 as f32);
+            let to = vec2(jump.to.1 as f32, jump.to.0 as f32);
+
+            from.lerp(to, ease(jump.elapsed / JUMP_DURATION))
+        })
+    }
+
+    /// The function moves the network to its position for the given orientation of the view.
+    /// A jump being animated is interrupted, since its path would cross the rotated map.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.jump = None;
+
+        for teleport in self.teleports.iter_mut() {
+            teleport.position = Camera::top_face_center(
+                self.map_len,
+                orientation,
+                teleport.coords.0,
+                teleport.coords.1,
+                &teleport.tile,
+            );
+        }
+
+        self.last_position = self.center(&self.last_tile, self.last_coords);
+    }
+
+    /// The function returns the center of the top face of the given tile, in world coordinates.
+    fn center(&self, tile: &Tile, coords: (usize, usize)) -> Vec2 {
+        Camera::top_face_center(self.map_len, self.orientation, coords.0, coords.1, tile)
+    }
+
+    /// The function draws a straight line between two points, in world coordinates.
+    fn draw_link(canvas: &mut Canvas, draw_param: DrawParam, start: Vec2, end: Vec2, scale: f32) {
+        let start = start * scale;
+        let direction = end * scale - start;
+
+        if direction.length() > 0.0 {
+            let normal = direction.perp().normalize() * LINK_THICKNESS * scale * 0.5;
+
+            canvas.draw(
+                &Quad,
+                draw_param
+                    .dest(start - normal)
+                    .rotation(direction.y.atan2(direction.x))
+                    .scale(vec2(direction.length(), LINK_THICKNESS * scale)),
+            );
+        }
+    }
+}
+
+impl<'a> Component<TeleportsComponentParam, TeleportsComponentUpdateParam<'a>>
+    for TeleportsComponent
+{
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: TeleportsComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        let scale = component_param.camera.scale();
+
+        if self.enabled {
+            // Links every pair of known teleports. The links between active teleports are
+            // drawn last, on top of the faded ones.
+            for active in [false, true] {
+                for (i, from) in self.teleports.iter().enumerate() {
+                    for to in self.teleports.iter().skip(i + 1) {
+                        if (from.active && to.active) != active {
+                            continue;
+                        }
+
+                        Self::draw_link(
+                            canvas,
+                            draw_param.color(if active {
+                                Color::new(0.0, 1.0, 1.0, 0.35)
+                            } else {
+                                Color::new(0.5, 0.5, 0.5, 0.2)
+                            }),
+                            from.position,
+                            to.position,
+                            scale,
+                        );
+                    }
+                }
+            }
+
+            // Marks active teleports in cyan and inactive ones in grey.
+            for teleport in self.teleports.iter() {
+                canvas.draw(
+                    &self.marker,
+                    draw_param
+                        .dest(teleport.position * scale)
+                        .scale(vec2(scale, scale))
+                        .color(if teleport.active {
+                            Color::CYAN
+                        } else {
+                            Color::new(0.5, 0.5, 0.5, 0.8)
+                        }),
+                );
+            }
+        }
+
+        // Animates the jump: the robot flies from the start to the end of the jump along an arc,
+        // while a ring closes on the start and another one opens on the end.
+        if let Some(jump) = &self.jump {
+            let t = ease(jump.elapsed / JUMP_DURATION);
+
+            Self::draw_link(
+                canvas,
+                draw_param.color(Color::new(1.0, 0.0, 1.0, 0.6 * (1.0 - t))),
+                jump.from_position,
+                jump.to_position,
+                scale,
+            );
+
+            for (position, size) in [(jump.from_position, 1.0 - t), (jump.to_position, t)] {
+                canvas.draw(
+                    &self.marker,
+                    draw_param
+                        .dest(position * scale)
+                        .scale(vec2(scale, scale) * size * 1.5)
+                        .color(Color::MAGENTA),
+                );
+            }
+
+            let arc = vec2(0.0, -(PI * t).sin() * 24.0);
+            let position = jump.from_position.lerp(jump.to_position, t) + arc;
+
+            // The image of the robot is drawn above the center of the tile.
+            canvas.draw(
+                &self.player,
+                draw_param
+                    .dest((position - Camera::face_center() - vec2(0.0, 2.0)) * scale)
+                    .scale(vec2(scale, scale))
+                    .color(component_param.tint),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        update_param: TeleportsComponentUpdateParam<'a>,
+    ) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            TeleportsComponentUpdateType::WorldVisibility(world) => {
+                self.teleports = world
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(row, tiles)| {
+                        tiles.iter().enumerate().filter_map(move |(col, tile)| {
+                            tile.as_ref().map(|tile| (row, col, tile))
+                        })
+                    })
+                    .filter_map(|(row, col, tile)| match tile.tile_type {
+                        TileType::Teleport(active) => Some(Teleport {
+                            coords: (row, col),
+                            tile: tile.clone(),
+                            position: self.center(tile, (row, col)),
+                            active,
+                        }),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            TeleportsComponentUpdateType::Moved(tile, coords) => {
+                if teleported(self.last_coords, coords, &tile) {
+                    self.jump = Some(Jump {
+                        from: self.last_coords,
+                        to: coords,
+                        from_position: self.last_position,
+                        to_position: self.center(&tile, coords),
+                        elapsed: 0.0,
+                    });
+                }
+
+                self.last_coords = coords;
+                self.last_position = self.center(&tile, coords);
+                self.last_tile = tile;
+            }
+            TeleportsComponentUpdateType::Tick(delta) => {
+                if let Some(jump) = self.jump.as_mut() {
+                    jump.elapsed += delta;
+
+                    if jump.elapsed >= JUMP_DURATION {
+                        self.jump = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TeleportsComponentParam {
+    /// The constructor creates a new instance of the teleports component parameters.
+    pub(crate) fn new(camera: Camera, tint: Color) -> Self {
+        Self { camera, tint }
+    }
+}
+
+impl<'a> TeleportsComponentUpdateParam<'a> {
+    /// The constructor creates a new instance of the teleports component update parameters.
+    pub(crate) fn new(_type: TeleportsComponentUpdateType<'a>) -> Self {
+        Self { _type }
+    }
+}
+
+/// The function eases the given progress between 0.0 and 1.0, so that the animation starts and
+/// ends slowly.
+fn ease(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
@@ -0,0 +1,284 @@
+use std::collections::VecDeque;
+
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, DrawParam, GraphicsContext, Mesh, Quad},
+};
+use robotics_lib::world::tile::Tile;
+
+use crate::visualizer::camera::{Camera, Orientation};
+
+use super::{teleported, Component};
+
+/// Thickness of the trail line, in world coordinates.
+const LINE_THICKNESS: f32 = 1.0;
+
+/// A single position of the robot in the trail.
+struct TrailStep {
+    coords: (usize, usize),
+    tile: Tile,
+    /// Center of the top face of the tile, in world coordinates.
+    position: Vec2,
+    /// Number of ticks the robot spent on the tile without moving.
+    stops: usize,
+    /// Whether the robot reached the tile jumping through a teleport.
+    teleported: bool,
+}
+
+/// The trail component implements Component and draws the last positions of the robot as a
+/// fading line, with markers where the robot stopped or teleported.
+pub(in crate::visualizer) struct TrailComponent {
+    steps: VecDeque<TrailStep>,
+    last_coords: (usize, usize),
+    moved: bool,
+    map_len: usize,
+    orientation: Orientation,
+    length: usize,
+    newest_color: Color,
+    oldest_color: Color,
+    stop_marker: Mesh,
+    teleport_marker: Mesh,
+    enabled: bool,
+}
+
+/// The struct contains the parameters required by the draw function.
+pub(in crate::visualizer) struct TrailComponentParam {
+    camera: Camera,
+}
+
+/// The union contains the types of updates for the component.
+pub(in crate::visualizer) enum TrailComponentUpdateType {
+    /// The robot has moved into the given tile.
+    Moved(Tile, (usize, usize)),
+    /// A tick has passed.
+    Tick,
+}
+
+/// The struct contains the parameters required by the update function.
+pub(in crate::visualizer) struct TrailComponentUpdateParam {
+    _type: TrailComponentUpdateType,
+}
+
+impl TrailComponent {
+    /// The constructor creates a new instance of the trail component, starting from the given
+    /// tile. The trail keeps at most length steps, colored from newest_color to oldest_color
+    /// by age.
+    pub(crate) fn new(
+        gfx: &impl Has<GraphicsContext>,
+        initial_tile: &Tile,
+        initial_position: (usize, usize),
+        map_len: usize,
+        length: usize,
+        newest_color: Color,
+        oldest_color: Color,
+    ) -> Self {
+        let stop_marker = Mesh::new_circle(
+            gfx,
+            DrawMode::fill(),
+            vec2(0.0, 0.0),
+            1.5,
+            0.1,
+            Color::WHITE,
+        )
+        .unwrap();
+        let teleport_marker = Mesh::new_circle(
+            gfx,
+            DrawMode::stroke(0.75),
+            vec2(0.0, 0.0),
+            4.0,
+            0.1,
+            Color::WHITE,
+        )
+        .unwrap();
+
+        let mut trail = Self {
+            steps: VecDeque::new(),
+            last_coords: initial_position,
+            moved: false,
+            map_len,
+            orientation: Orientation::North,
+            length,
+            newest_color,
+            oldest_color,
+            stop_marker,
+            teleport_marker,
+            enabled: true,
+        };
+
+        trail.push(initial_tile, initial_position, false);
+        trail
+    }
+
+    /// The function shows or hides the trail and returns the new state.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    /// The function sets the maximum number of steps of the trail, dropping the oldest ones.
+    pub(crate) fn set_length(&mut self, length: usize) {
+        self.length = length.max(1);
+
+        while self.steps.len() > self.length {
+            self.steps.pop_front();
+        }
+    }
+
+    /// The function returns the maximum number of steps of the trail.
+    pub(crate) fn length(&self) -> usize {
+        self.length
+    }
+
+    /// The function moves the steps to their position for the given orientation of the view.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+
+        for step in self.steps.iter_mut() {
+            step.position = Camera::top_face_center(
+                self.map_len,
+                orientation,
+                step.coords.0,
+                step.coords.1,
+                &step.tile,
+            );
+        }
+    }
+
+    /// The function appends a new step to the trail.
+    fn push(&mut self, tile: &Tile, coords: (usize, usize), teleported: bool) {
+        let position =
+            Camera::top_face_center(self.map_len, self.orientation, coords.0, coords.1, tile);
+
+        self.steps.push_back(TrailStep {
+            coords,
+            tile: tile.clone(),
+            position,
+            stops: 0,
+            teleported,
+        });
+
+        if self.steps.len() > self.length {
+            self.steps.pop_front();
+        }
+    }
+
+    /// The function returns the color of a step of the given age, where 0.0 is the newest step
+    /// and 1.0 the oldest one. Older steps are also more transparent.
+    fn color(&self, age: f32) -> Color {
+        let from = self.newest_color;
+        let to = self.oldest_color;
+
+        Color::new(
+            from.r + (to.r - from.r) * age,
+            from.g + (to.g - from.g) * age,
+            from.b + (to.b - from.b) * age,
+            (from.a + (to.a - from.a) * age) * (1.0 - age * 0.8),
+        )
+    }
+}
+
+impl Component<TrailComponentParam, TrailComponentUpdateParam> for TrailComponent {
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        draw_param: DrawParam,
+        component_param: TrailComponentParam,
+    ) -> Result<(), ggez::GameError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let scale = component_param.camera.scale();
+        let count = self.steps.len().max(2) as f32;
+
+        for (i, step) in self.steps.iter().enumerate() {
+            // The newest step is the last one of the queue.
+            let age = 1.0 - i as f32 / (count - 1.0);
+            let color = self.color(age);
+
+            // Draws the segment from the previous step, unless the robot teleported here.
+            if let (Some(previous), false) = (i.checked_sub(1), step.teleported) {
+                let start = self.steps[previous].position * scale;
+                let end = step.position * scale;
+                let direction = end - start;
+
+                if direction.length() > 0.0 {
+                    let normal = direction.perp().normalize() * LINE_THICKNESS * scale * 0.5;
+
+                    // The quad is rotated around its top left corner, which is shifted by half
+                    // of the thickness to center the line on the steps.
+                    canvas.draw(
+                        &Quad,
+                        draw_param
+                            .dest(start - normal)
+                            .rotation(direction.y.atan2(direction.x))
+                            .scale(vec2(direction.length(), LINE_THICKNESS * scale))
+                            .color(color),
+                    );
+                }
+            }
+
+            // Draws the markers of stops and teleports.
+            if step.stops > 0 {
+                let size = scale * (1.0 + step.stops.min(5) as f32 * 0.25);
+
+                canvas.draw(
+                    &self.stop_marker,
+                    draw_param
+                        .dest(step.position * scale)
+                        .scale(vec2(size, size))
+                        .color(color),
+                );
+            }
+
+            if step.teleported {
+                canvas.draw(
+                    &self.teleport_marker,
+                    draw_param
+                        .dest(step.position * scale)
+                        .scale(vec2(scale, scale))
+                        .color(Color::MAGENTA),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, update_param: TrailComponentUpdateParam) -> Result<(), ggez::GameError> {
+        match update_param._type {
+            TrailComponentUpdateType::Moved(tile, coords) => {
+                self.push(&tile, coords, teleported(self.last_coords, coords, &tile));
+                self.last_coords = coords;
+                self.moved = true;
+            }
+            TrailComponentUpdateType::Tick => {
+                // The robot stopped if it didn't move during the whole tick.
+                if !self.moved {
+                    if let Some(step) = self.steps.back_mut() {
+                        step.stops += 1;
+                    }
+                }
+
+                self.moved = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TrailComponentParam {
+    /// The constructor creates a new instance of the trail component parameters.
+    pub(crate) fn new(camera: Camera) -> Self {
+        Self { camera }
+    }
+}
+
+impl TrailComponentUpdateParam {
+    /// The constructor creates a new instance of the trail component update parameters.
+    pub(crate) fn new(_type: TrailComponentUpdateType) -> Self {
+        Self { _type }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use ggez::graphics::Color;
+use robotics_lib::world::tile::Tile;
+
+/// Number of ticks after which a tile not observed anymore is completely faded.
+const FADE_TICKS: f32 = 60.0;
+
+/// Color of the tiles hidden by the fog.
+const DIMMED: Color = Color::new(0.0, 0.0, 0.0, 127.0 / 255.0);
+
+/// The modes of the fog of war, which decide how the tiles unknown to the robot are drawn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum FogMode {
+    /// The whole world is shown, as if every tile was known.
+    Omniscient,
+    /// Only the tiles known by the robot are shown.
+    KnownOnly,
+    /// The tiles unknown to the robot are dimmed.
+    Dimmed,
+    /// The tiles are faded by the time passed since the robot last observed them.
+    RecentlySeen,
+}
+
+/// The struct contains the fog of war, which remembers when every tile has been observed for
+/// the last time.
+/// The robot is assumed to observe the known tiles around it on every tick.
+/// The fog also collects the tiles whose color has changed, so only those are colored again.
+/// The tiles still fading are kept apart, as their color changes on every tick.
+pub(in crate::visualizer) struct Fog {
+    mode: FogMode,
+    tick: usize,
+    position: (usize, usize),
+    last_seen: Vec<Vec<Option<usize>>>,
+    changed: HashSet<(usize, usize)>,
+    fading: HashSet<(usize, usize)>,
+}
+
+impl Fog {
+    /// The constructor creates the fog of a map of the given size, with the robot in the given
+    /// position. No tile is known at the beginning.
+    pub(in crate::visualizer) fn new(map_len: usize, initial_position: (usize, usize)) -> Self {
+        Self {
+            mode: FogMode::Dimmed,
+            tick: 0,
+            position: initial_position,
+            last_seen: vec![vec![None; map_len]; map_len],
+            changed: HashSet::new(),
+            fading: HashSet::new(),
+        }
+    }
+
+    /// The function cycles between the modes of the fog and returns the new one.
+    pub(in crate::visualizer) fn cycle_mode(&mut self) -> FogMode {
+        self.mode = match self.mode {
+            FogMode::Dimmed => FogMode::RecentlySeen,
+            FogMode::RecentlySeen => FogMode::KnownOnly,
+            FogMode::KnownOnly => FogMode::Omniscient,
+            FogMode::Omniscient => FogMode::Dimmed,
+        };
+
+        self.mode
+    }
+
+    /// The function marks as observed in the current tick the tiles which have just been
+    /// discovered by the robot, and returns their coordinates (row, column).
+    pub(in crate::visualizer) fn observe(
+        &mut self,
+        world: &[Vec<Option<Tile>>],
+    ) -> Vec<(usize, usize)> {
+        let mut discovered = Vec::new();
+
+        for (row, tiles) in world.iter().enumerate() {
+            for (col, tile) in tiles.iter().enumerate() {
+                if tile.is_some() && self.last_seen[row][col].is_none() {
+                    self.see(row, col);
+                    discovered.push((row, col));
+                }
+            }
+        }
+
+        discovered
+    }
+
+    /// The function tells whether the tile in the given row and column is known by the robot.
+    pub(in crate::visualizer) fn is_known(&self, row: usize, col: usize) -> bool {
+        self.last_seen[row][col].is_some()
+    }
+
+    /// The function moves the robot into the given position.
+    pub(in crate::visualizer) fn move_to(&mut self, coords: (usize, usize)) {
+        self.position = coords;
+        self.observe_around();
+    }
+
+    /// The function advances the fog by one tick.
+    pub(in crate::visualizer) fn advance(&mut self) {
+        self.tick += 1;
+
+        // The tiles seen recently fade a bit more, until they're completely faded.
+        if self.mode == FogMode::RecentlySeen {
+            self.changed.extend(self.fading.iter().copied());
+        }
+        let tick = self.tick;
+        let last_seen = &self.last_seen;
+        self.fading.retain(|(row, col)| {
+            last_seen[*row][*col].is_some_and(|seen| ((tick - seen) as f32) < FADE_TICKS)
+        });
+
+        self.observe_around();
+    }
+
+    /// The function returns the coordinates (row, column) of the tiles whose color has changed
+    /// since the previous call. Changing the mode changes the color of every tile, which isn't
+    /// collected.
+    pub(in crate::visualizer) fn take_changed(&mut self) -> Vec<(usize, usize)> {
+        self.changed.drain().collect()
+    }
+
+    /// The function marks the tile in the given row and column as observed in the current tick.
+    fn see(&mut self, row: usize, col: usize) {
+        self.last_seen[row][col] = Some(self.tick);
+        self.changed.insert((row, col));
+        self.fading.insert((row, col));
+    }
+
+    /// The function marks as observed the known tiles adjacent to the robot.
+    fn observe_around(&mut self) {
+        let map_len = self.last_seen.len();
+        let (row, col) = self.position;
+
+        for r in row.saturating_sub(1)..=(row + 1).min(map_len - 1) {
+            for c in col.saturating_sub(1)..=(col + 1).min(map_len - 1) {
+                if self.last_seen[r][c].is_some() {
+                    self.see(r, c);
+                }
+            }
+        }
+    }
+
+    /// The function returns the color of the tile in the given row and column for the current
+    /// mode.
+    pub(in crate::visualizer) fn color(&self, row: usize, col: usize) -> Color {
+        let last_seen = self.last_seen[row][col];
+
+        match (self.mode, last_seen) {
+            (FogMode::Omniscient, _) => Color::WHITE,
+            (FogMode::KnownOnly, None) => Color::new(0.0, 0.0, 0.0, 0.0),
+            (FogMode::Dimmed, None) | (FogMode::RecentlySeen, None) => DIMMED,
+            (FogMode::KnownOnly, Some(_)) | (FogMode::Dimmed, Some(_)) => Color::WHITE,
+            (FogMode::RecentlySeen, Some(tick)) => {
+                let t = ((self.tick - tick) as f32 / FADE_TICKS).min(1.0);
+
+                Color::new(1.0 - t, 1.0 - t, 1.0 - t, 1.0 + (DIMMED.a - 1.0) * t)
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use ggez::{
+    context::Has,
+    glam::{vec2, Vec2},
+    graphics::{GraphicsContext, Rect},
+};
+
+/// Space left between the panels and the edges of the window.
+pub(in crate::visualizer) const MARGIN: f32 = 10.0;
+
+/// The corners of the window a panel can be anchored to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// The panels of the interface placed by the layout.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::visualizer) enum Panel {
+    Dialog,
+    Selection,
+    Hud,
+    Score,
+    Legend,
+    Filter,
+    Elevation,
+}
+
+impl Panel {
+    /// Number of panels of the interface.
+    const COUNT: usize = 7;
+
+    /// The function returns the corner of the window the panel is anchored to.
+    pub(in crate::visualizer) fn anchor(self) -> Anchor {
+        match self {
+            Panel::Dialog | Panel::Selection => Anchor::TopLeft,
+            Panel::Hud | Panel::Score | Panel::Legend => Anchor::TopRight,
+            Panel::Elevation => Anchor::BottomLeft,
+            Panel::Filter => Anchor::BottomRight,
+        }
+    }
+}
+
+/// The struct places the panels of the interface, which are drawn on a separate screen-space
+/// pass on top of the map, so they stay fixed while the camera moves.
+/// Sizes and positions are in logical pixels: the pass maps them on the physical pixels of the
+/// window with its scale factor, so the panels keep their size on HiDPI displays.
+/// The panels anchored to the same corner are stacked by their size, moving away from the
+/// corner in the order they're added.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(in crate::visualizer) struct Layout {
+    size: Vec2,
+    scale_factor: f32,
+    stacks: [f32; 4],
+    positions: [Option<Vec2>; Panel::COUNT],
+}
+
+impl Layout {
+    /// The constructor creates the layout of the current size and scale factor of the window.
+    pub(in crate::visualizer) fn new(gfx: &impl Has<GraphicsContext>) -> Self {
+        let window = gfx.retrieve().window();
+        let size = window.inner_size();
+
+        Self::from_physical(
+            vec2(size.width as f32, size.height as f32),
+            window.scale_factor() as f32,
+        )
+    }
+
+    /// The constructor creates the layout of a window with the given size in physical pixels
+    /// and the given scale factor.
+    pub(in crate::visualizer) fn from_physical(size: Vec2, scale_factor: f32) -> Self {
+        // A broken scale factor would hide the whole interface.
+        let scale_factor = if scale_factor > 0.0 {
+            scale_factor
+        } else {
+            1.0
+        };
+
+        Self {
+            size: size / scale_factor,
+            scale_factor,
+            stacks: [0.0; 4],
+            positions: [None; Panel::COUNT],
+        }
+    }
+
+    /// The function returns the screen coordinates of the screen-space pass.
+    pub(in crate::visualizer) fn screen_coordinates(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.size.x, self.size.y)
+    }
+
+    /// The function converts a point of the window from physical to logical pixels, as mouse
+    /// positions are reported in physical pixels.
+    pub(in crate::visualizer) fn to_logical(self, point: Vec2) -> Vec2 {
+        point / self.scale_factor
+    }
+
+    /// The function returns the width available to a panel between the margins of the window,
+    /// up to the given preferred width.
+    pub(in crate::visualizer) fn fit_width(&self, width: f32) -> f32 {
+        width.min(self.size.x - MARGIN * 2.0).max(0.0)
+    }
+
+    /// The function returns the top left corner of a panel of the given size, anchored to a
+    /// corner of the window. The offset is the distance between the anchored corner of the
+    /// panel and the one of the window.
+    fn place(&self, anchor: Anchor, offset: Vec2, size: Vec2) -> Vec2 {
+        let position = match anchor {
+            Anchor::TopLeft => offset,
+            Anchor::TopRight => vec2(self.size.x - size.x - offset.x, offset.y),
+            Anchor::BottomLeft => vec2(offset.x, self.size.y - size.y - offset.y),
+            Anchor::BottomRight => self.size - size - offset,
+        };
+
+        self.clamp(position, size)
+    }
+
+    /// The function stacks the given panel on its corner, after the panels already added to
+    /// the same corner. Hidden panels have no size and take no space.
+    pub(in crate::visualizer) fn stack(&mut self, panel: Panel, size: Option<Vec2>) {
+        let Some(size) = size else {
+            self.positions[panel as usize] = None;
+            return;
+        };
+
+        let anchor = panel.anchor();
+        let offset = vec2(MARGIN, MARGIN + self.stacks[anchor as usize]);
+
+        self.positions[panel as usize] = Some(self.place(anchor, offset, size));
+        self.stacks[anchor as usize] += size.y + MARGIN;
+    }
+
+    /// The function returns the top left corner of the given panel, if it has been stacked.
+    pub(in crate::visualizer) fn position(&self, panel: Panel) -> Option<Vec2> {
+        self.positions[panel as usize]
+    }
+
+    /// The function moves the top left corner of a panel of the given size so the panel stays
+    /// inside the window. Panels larger than the window keep their top left corner visible.
+    pub(in crate::visualizer) fn clamp(&self, position: Vec2, size: Vec2) -> Vec2 {
+        position.min(self.size - size).max(Vec2::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn place_anchors_panels_to_the_corners() {
+        let layout = Layout::from_physical(vec2(800.0, 600.0), 1.0);
+        let size = vec2(100.0, 50.0);
+        let offset = vec2(MARGIN, MARGIN);
+
+        assert_eq!(
+            layout.place(Anchor::TopLeft, offset, size),
+            vec2(10.0, 10.0)
+        );
+        assert_eq!(
+            layout.place(Anchor::TopRight, offset, size),
+            vec2(690.0, 10.0)
+        );
+        assert_eq!(
+            layout.place(Anchor::BottomLeft, offset, size),
+            vec2(10.0, 540.0)
+        );
+        assert_eq!(
+            layout.place(Anchor::BottomRight, offset, size),
+            vec2(690.0, 540.0)
+        );
+    }
+
+    #[test]
+    fn scale_factor_shrinks_the_logical_window() {
+        let layout = Layout::from_physical(vec2(1600.0, 1200.0), 2.0);
+
+        assert_eq!(
+            layout.screen_coordinates(),
+            Rect::new(0.0, 0.0, 800.0, 600.0)
+        );
+        assert_eq!(layout.to_logical(vec2(400.0, 300.0)), vec2(200.0, 150.0));
+        assert_eq!(
+            layout.place(Anchor::TopRight, vec2(MARGIN, MARGIN), vec2(100.0, 50.0)),
+            vec2(690.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn stack_places_panels_of_the_same_corner_apart() {
+        let mut layout = Layout::from_physical(vec2(800.0, 600.0), 1.0);
+
+        layout.stack(Panel::Hud, Some(vec2(400.0, 120.0)));
+        layout.stack(Panel::Score, None);
+        layout.stack(Panel::Legend, Some(vec2(400.0, 200.0)));
+        layout.stack(Panel::Dialog, Some(vec2(500.0, 100.0)));
+        layout.stack(Panel::Filter, Some(vec2(200.0, 100.0)));
+
+        assert_eq!(layout.position(Panel::Hud), Some(vec2(390.0, 10.0)));
+        assert_eq!(layout.position(Panel::Score), None);
+        assert_eq!(layout.position(Panel::Legend), Some(vec2(390.0, 140.0)));
+        assert_eq!(layout.position(Panel::Dialog), Some(vec2(10.0, 10.0)));
+        assert_eq!(layout.position(Panel::Filter), Some(vec2(590.0, 490.0)));
+        assert_eq!(layout.position(Panel::Selection), None);
+    }
+
+    #[test]
+    fn panels_stay_inside_small_windows() {
+        let layout = Layout::from_physical(vec2(300.0, 200.0), 1.0);
+
+        assert_eq!(layout.fit_width(900.0), 280.0);
+        assert_eq!(
+            layout.place(Anchor::TopRight, vec2(MARGIN, MARGIN), vec2(400.0, 50.0)),
+            vec2(0.0, 10.0)
+        );
+        assert_eq!(
+            layout.clamp(vec2(250.0, 180.0), vec2(100.0, 50.0)),
+            vec2(200.0, 150.0)
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use ggez::graphics::Color;
+use robotics_lib::world::environmental_conditions::{DayTime, EnvironmentalConditions};
+
+/// Key frames of the day, as (minutes since midnight, map tint, sky color).
+/// Colors between two key frames are linearly interpolated.
+const KEY_FRAMES: [(f32, u32, u32); 8] = [
+    (0.0, 0x5A6AA6, 0x0B1030),
+    (300.0, 0x5A6AA6, 0x0B1030),
+    (420.0, 0xFFD9BF, 0xF4B183),
+    (540.0, 0xFFFFFF, 0xADD8E6),
+    (1020.0, 0xFFFFFF, 0xADD8E6),
+    (1140.0, 0xFFBF99, 0xF08A5D),
+    (1260.0, 0x5A6AA6, 0x0B1030),
+    (1440.0, 0x5A6AA6, 0x0B1030),
+];
+
+/// The struct contains the global lighting of the scene, computed from the in-game time.
+/// The tint multiplies the colors of the map components, the sky is the background color.
+pub(in crate::visualizer) struct Lighting {
+    tint: Color,
+    sky: Color,
+}
+
+impl Lighting {
+    /// The constructor creates the lighting corresponding to the time of the given environment.
+    pub(in crate::visualizer) fn from_environment(environment: &EnvironmentalConditions) -> Self {
+        // The time string has the "HH:MM" format. If it can't be parsed, the time is
+        // approximated from the part of the day.
+        let minutes = parse_minutes(&environment.get_time_of_day_string()).unwrap_or(
+            match environment.get_time_of_day() {
+                DayTime::Morning => 600.0,
+                DayTime::Afternoon => 900.0,
+                DayTime::Night => 0.0,
+            },
+        );
+
+        Self::from_minutes(minutes)
+    }
+
+    /// The function computes the lighting for the given minutes since midnight.
+    fn from_minutes(minutes: f32) -> Self {
+        let minutes = minutes.rem_euclid(1440.0);
+
+        // Finds the pair of key frames containing the current time.
+        let (start, end) = KEY_FRAMES
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .find(|(start, end)| minutes >= start.0 && minutes <= end.0)
+            .unwrap_or((KEY_FRAMES[0], KEY_FRAMES[1]));
+
+        let t = (minutes - start.0) / (end.0 - start.0);
+
+        Self {
+            tint: lerp_color(Color::from_rgb_u32(start.1), Color::from_rgb_u32(end.1), t),
+            sky: lerp_color(Color::from_rgb_u32(start.2), Color::from_rgb_u32(end.2), t),
+        }
+    }
+
+    /// The function returns the color which tints the map.
+    pub(in crate::visualizer) fn tint(&self) -> Color {
+        self.tint
+    }
+
+    /// The function returns the background color of the sky.
+    pub(in crate::visualizer) fn sky(&self) -> Color {
+        self.sky
+    }
+}
+
+/// The function parses a "HH:MM" string into the minutes since midnight.
+fn parse_minutes(time: &str) -> Option<f32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let hours = hours.parse::<u32>().ok()?;
+    let minutes = minutes.parse::<u32>().ok()?;
+
+    Some((hours * 60 + minutes) as f32)
+}
+
+/// The function linearly interpolates two colors.
+fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    Color::new(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+        from.a + (to.a - from.a) * t,
+    )
+}
@@ -1,54 +1,153 @@
+mod camera;
 mod components;
+mod fog;
+mod layout;
+mod lighting;
 mod textures;
 
 use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use ggez::graphics::{DrawParam, Rect};
+use ggez::graphics::{Color, DrawParam, Image, ImageEncodingFormat, ImageFormat, Rect};
 use ggez::{
     context::Has,
     glam::{vec2, Vec2},
-    graphics::{Canvas, Color, FilterMode, GraphicsContext, Sampler},
+    graphics::{Canvas, FilterMode, GraphicsContext, Sampler},
 };
 use ggez::{Context, GameResult};
 use robotics_lib::event::events::Event;
 use robotics_lib::runner::Runner;
 use robotics_lib::utils::LibError;
-use robotics_lib::world::tile::Tile;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::{Content, Tile};
 
+use crate::session::Bookmark;
 use crate::visualizer::components::contents_map::ContentsMapComponentUpdateType;
 
+use self::camera::{Camera, FollowMode};
 use self::components::contents_map::{
     ContentsMapComponent, ContentsMapComponentParam, ContentsMapComponentUpdateParam,
 };
 use self::components::dialog::{DialogComponent, DialogComponentParam, DialogComponentUpdateParam};
-use self::components::player::{PlayerComponent, PlayerComponentParam, PlayerComponentUpdateParam};
+use self::components::elevation::{ElevationComponent, ElevationComponentParam};
+use self::components::filter::{
+    dim, FilterComponent, FilterComponentParam, FilterComponentUpdateParam,
+    FilterComponentUpdateType,
+};
+use self::components::frontier::{
+    FrontierComponent, FrontierComponentParam, FrontierComponentUpdateParam,
+    FrontierComponentUpdateType,
+};
+use self::components::grid::{
+    GridComponent, GridComponentParam, GridComponentUpdateParam, GridComponentUpdateType,
+};
+use self::components::heatmap::{
+    HeatmapComponent, HeatmapComponentParam, HeatmapComponentUpdateParam,
+    HeatmapComponentUpdateType, HeatmapMetric,
+};
+use self::components::hud::{
+    HudComponent, HudComponentParam, HudComponentUpdateParam, HudComponentUpdateType,
+};
+use self::components::inspector::{
+    HoveredTile, InspectorComponent, InspectorComponentParam, InspectorComponentUpdateParam,
+};
+use self::components::legend::{
+    LegendComponent, LegendComponentParam, LegendComponentUpdateParam, LegendComponentUpdateType,
+};
+use self::components::particles::{
+    ParticlesComponent, ParticlesComponentParam, ParticlesComponentUpdateParam,
+    ParticlesComponentUpdateType,
+};
+use self::components::player::{
+    PlayerComponent, PlayerComponentParam, PlayerComponentUpdateParam, PlayerComponentUpdateType,
+};
+use self::components::score::{
+    ScoreComponent, ScoreComponentParam, ScoreComponentUpdateParam, ScoreComponentUpdateType,
+};
+use self::components::selection::{
+    SelectionComponent, SelectionComponentParam, SelectionComponentUpdateParam,
+    SelectionComponentUpdateType,
+};
+use self::components::spotlight::{
+    SpotlightComponent, SpotlightComponentParam, SpotlightComponentUpdateParam,
+    SpotlightComponentUpdateType,
+};
 use self::components::tails_map::{
     TilesMapComponent, TilesMapComponentParam, TilesMapComponentUpdateParam,
 };
+use self::components::teleports::{
+    TeleportsComponent, TeleportsComponentParam, TeleportsComponentUpdateParam,
+    TeleportsComponentUpdateType,
+};
+use self::components::trail::{
+    TrailComponent, TrailComponentParam, TrailComponentUpdateParam, TrailComponentUpdateType,
+};
 use self::components::Component;
+use self::fog::{Fog, FogMode};
+use self::layout::{Layout, Panel};
+use self::lighting::Lighting;
+
+/// Default number of steps of the robot trail.
+const TRAIL_LENGTH: usize = 200;
+
+/// Rate at which the camera approaches its target center, per second.
+const CAMERA_EASING: f32 = 6.0;
+
+/// Bounds of the zoom of the camera.
+const ZOOM: (f32, f32) = (0.5, 6.0);
+
+/// Bounds in seconds of the duration of the movement of the robot between two tiles.
+const MOVE_DURATION: (f32, f32) = (0.1, 1.0);
+
+/// Maximum seconds between the two clicks of a double click.
+const DOUBLE_CLICK_TIME: f32 = 0.4;
 
 pub struct Visualizer {
     // Shared states
     runner: Runner,
     event_queue_rc: Rc<RefCell<VecDeque<Event>>>,
     world_rc: Rc<RefCell<Option<Vec<Vec<Option<Tile>>>>>>,
+    map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
+    fog_rc: Rc<RefCell<Fog>>,
 
     // Visualization variables
     map_size: Vec2,
-    origin: Vec2,
-    scale: f32,
+    camera: Camera,
+    initial_scale: f32,
+    cursor: Option<Vec2>,
+    follow_mode: FollowMode,
+    center_target: Option<Vec2>,
+    last_moved: f32,
+    last_click: Option<(f32, Vec2)>,
+    elapsed: f32,
+    lighting: Lighting,
 
     // Components
     tiles_map_component: TilesMapComponent,
     contents_map_component: ContentsMapComponent,
     player_component: PlayerComponent,
     dialog_component: DialogComponent,
+    hud_component: HudComponent,
+    particles_component: ParticlesComponent,
+    score_component: ScoreComponent,
+    inspector_component: InspectorComponent,
+    grid_component: GridComponent,
+    trail_component: TrailComponent,
+    heatmap_component: HeatmapComponent,
+    elevation_component: ElevationComponent,
+    filter_component: FilterComponent,
+    legend_component: LegendComponent,
+    frontier_component: FrontierComponent,
+    teleports_component: TeleportsComponent,
+    spotlight_component: SpotlightComponent,
+    selection_component: SelectionComponent,
 }
 
 impl Visualizer {
     /// Create a new instance of the Visualizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         gfx: &impl Has<GraphicsContext>,
         runner: Runner,
@@ -57,40 +156,177 @@ impl Visualizer {
         map_rc: Rc<RefCell<Vec<Vec<Tile>>>>,
         initial_position: (usize, usize),
         initial_scale: f32,
+        initial_environment: EnvironmentalConditions,
+        max_score: f32,
+        score_table: Option<HashMap<Content, f32>>,
     ) -> Self {
         // Size of square matrix.
         let map_len = map_rc.clone().borrow().len();
 
+        // Fog of war shared by the map components.
+        let fog_rc = Rc::new(RefCell::new(Fog::new(map_len, initial_position)));
+
         // Instance of the visualizer's components.
-        let tiles_map_component = TilesMapComponent::from_map(gfx, map_rc.clone());
-        let contents_map_component = ContentsMapComponent::from_map(gfx, map_rc.clone());
-        let player_component = PlayerComponent::new(gfx, initial_position, (map_len, map_len));
-        let dialog_component =
-            DialogComponent::new(gfx, "Robot is sleeping...\nZzzZzzzZzzzz".to_string());
+        let tiles_map_component = TilesMapComponent::from_map(gfx, map_rc.clone(), fog_rc.clone());
+        let contents_map_component =
+            ContentsMapComponent::from_map(gfx, map_rc.clone(), fog_rc.clone());
+        let player_component = PlayerComponent::new(
+            gfx,
+            &map_rc.borrow()[initial_position.0][initial_position.1],
+            initial_position,
+            (map_len, map_len),
+        );
+        let dialog_component = DialogComponent::new(
+            gfx,
+            "Robot is sleeping...\nZzzZzzzZzzzz".to_string(),
+            Layout::new(gfx),
+        );
+        let hud_component = HudComponent::new(gfx, &initial_environment, FollowMode::Always);
+        let particles_component =
+            ParticlesComponent::new(gfx, initial_environment.get_weather_condition());
+        let score_component = ScoreComponent::new(gfx, max_score, score_table);
+        let inspector_component = InspectorComponent::new(gfx, map_len);
+        let grid_component = GridComponent::from_map(gfx, map_rc.clone(), fog_rc.clone());
+        let trail_component = TrailComponent::new(
+            gfx,
+            &map_rc.borrow()[initial_position.0][initial_position.1],
+            initial_position,
+            map_len,
+            TRAIL_LENGTH,
+            Color::from_rgb_u32(0xFF4500),
+            Color::from_rgb_u32(0xFFD700),
+        );
+        let heatmap_component = HeatmapComponent::new(
+            gfx,
+            &map_rc.borrow()[initial_position.0][initial_position.1],
+            initial_position,
+            map_len,
+        );
+        let elevation_component = ElevationComponent::from_map(gfx, map_rc.clone(), fog_rc.clone());
+        let filter_component = FilterComponent::from_map(gfx, map_rc.clone());
+        let legend_component = LegendComponent::new(gfx);
+        let frontier_component = FrontierComponent::new(gfx);
+        let teleports_component = TeleportsComponent::new(
+            gfx,
+            &map_rc.borrow()[initial_position.0][initial_position.1],
+            initial_position,
+            map_len,
+        );
+        // The distance of the spotlight depends on the robot, so the preview is empty until it's
+        // set.
+        let spotlight_component =
+            SpotlightComponent::from_map(gfx, map_rc.clone(), initial_position, 0);
+        let selection_component = SelectionComponent::new(gfx, map_len);
+        let lighting = Lighting::from_environment(&initial_environment);
 
         Self {
             runner,
             event_queue_rc,
             world_rc,
+            map_rc,
+            fog_rc,
             map_size: vec2(map_len as f32, map_len as f32),
-            origin: vec2(0.0, 0.0),
-            scale: initial_scale,
+            camera: Camera::new(initial_scale),
+            initial_scale,
+            cursor: None,
+            follow_mode: FollowMode::Always,
+            center_target: None,
+            last_moved: 0.0,
+            last_click: None,
+            elapsed: 0.0,
+            lighting,
             tiles_map_component,
             contents_map_component,
             player_component,
             dialog_component,
+            hud_component,
+            particles_component,
+            score_component,
+            inspector_component,
+            grid_component,
+            trail_component,
+            heatmap_component,
+            elevation_component,
+            filter_component,
+            legend_component,
+            frontier_component,
+            teleports_component,
+            spotlight_component,
+            selection_component,
         }
     }
 
     /// The functions uses ctx for drawing the visualizer's components on the canvas.
     pub fn draw(&mut self, ctx: &mut Context) -> ggez::GameResult {
-        // Initialize the canvas with lightblue background.
-        let mut canvas = Canvas::from_frame(&ctx.gfx, Color::from_rgb_u32(0xADD8E6));
+        // Initialize the canvas with the sky color of the current time of day.
+        let canvas = Canvas::from_frame(&ctx.gfx, self.lighting.sky());
+
+        self.render(ctx, canvas)
+    }
+
+    /// Saves a PNG image of the visualizer in the user data directory.
+    pub fn screenshot(&mut self, ctx: &mut Context) -> GameResult {
+        let size = ctx.gfx.window().inner_size();
+
+        // The frame format can't be encoded, so the visualizer is rendered on a separate image.
+        let image = Image::new_canvas_image(
+            &ctx.gfx,
+            ImageFormat::Rgba8UnormSrgb,
+            size.width,
+            size.height,
+            1,
+        );
+        let canvas = Canvas::from_image(&ctx.gfx, image.clone(), self.lighting.sky());
+        self.render(ctx, canvas)?;
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let path = format!("/screenshot-{}.png", seconds);
+        image.encode(ctx, ImageEncodingFormat::Png, &path)?;
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Screenshot saved as {}",
+                path.trim_start_matches('/')
+            )))
+    }
+
+    /// Shows a message in the dialog.
+    pub fn show_message(&mut self, message: &str) -> GameResult {
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(message.to_string()))
+    }
+
+    /// Fits the panels to the window after it has been resized or moved to a display with a
+    /// different scale factor.
+    pub fn resize(&mut self, gfx: &impl Has<GraphicsContext>) {
+        self.dialog_component.resize(gfx, Layout::new(gfx));
+    }
+
+    /// The function places the visible panels on the layout of the window. Panels anchored to
+    /// the same corner are stacked in the order they're listed here.
+    fn layout(&self, gfx: &impl Has<GraphicsContext>) -> Layout {
+        let mut layout = Layout::new(gfx);
 
+        layout.stack(Panel::Dialog, Some(self.dialog_component.size()));
+        layout.stack(Panel::Selection, self.selection_component.panel_size());
+        layout.stack(Panel::Hud, Some(self.hud_component.size()));
+        layout.stack(Panel::Score, Some(self.score_component.size()));
+        layout.stack(Panel::Legend, self.legend_component.size());
+        layout.stack(Panel::Filter, self.filter_component.size());
+        layout.stack(Panel::Elevation, self.elevation_component.legend_size());
+
+        layout
+    }
+
+    /// The function draws the components of the visualizer on the given canvas.
+    fn render(&mut self, ctx: &mut Context, mut canvas: Canvas) -> GameResult {
         // Set left-top corner into the origin.
         canvas.set_screen_coordinates(Rect::new(
-            self.origin.x,
-            self.origin.y,
+            self.camera.origin().x,
+            self.camera.origin().y,
             ctx.gfx.window().inner_size().width as f32,
             ctx.gfx.window().inner_size().height as f32,
         ));
@@ -102,17 +338,24 @@ impl Visualizer {
         sampler.min = FilterMode::Nearest;
         canvas.set_sampler(sampler);
 
+        // The terrain is dimmed while the contents are filtered, so the matches stand out.
+        let tiles_tint = if self.filter_component.selected().is_empty() {
+            self.lighting.tint()
+        } else {
+            dim(self.lighting.tint())
+        };
+
         // Print the tiles component.
         self.tiles_map_component.draw(
             &mut canvas,
             DrawParam::new(),
             TilesMapComponentParam::new(
-                self.origin(),
+                self.camera,
                 vec2(
                     ctx.gfx.window().inner_size().width as f32,
                     ctx.gfx.window().inner_size().height as f32,
                 ),
-                self.scale,
+                tiles_tint,
             ),
         )?;
 
@@ -120,105 +363,871 @@ impl Visualizer {
         self.contents_map_component.draw(
             &mut canvas,
             DrawParam::new(),
-            ContentsMapComponentParam::new(self.scale),
+            ContentsMapComponentParam::new(
+                self.camera,
+                vec2(
+                    ctx.gfx.window().inner_size().width as f32,
+                    ctx.gfx.window().inner_size().height as f32,
+                ),
+                self.lighting.tint(),
+                self.elapsed,
+            ),
         )?;
 
-        // Print the player component
-        self.player_component.draw(
+        // Print the elevation component
+        self.elevation_component.draw(
             &mut canvas,
             DrawParam::new(),
-            PlayerComponentParam::new(self.scale),
+            ElevationComponentParam::new(self.camera),
+        )?;
+
+        // Print the heatmap component
+        self.heatmap_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            HeatmapComponentParam::new(self.camera),
+        )?;
+
+        // Print the spotlight component
+        self.spotlight_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            SpotlightComponentParam::new(self.camera),
+        )?;
+
+        // Print the frontier component
+        self.frontier_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            FrontierComponentParam::new(self.camera),
+        )?;
+
+        // Print the trail component
+        self.trail_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            TrailComponentParam::new(self.camera),
+        )?;
+
+        // Print the teleports component
+        self.teleports_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            TeleportsComponentParam::new(self.camera, self.lighting.tint()),
+        )?;
+
+        // Print the player component, unless it's jumping through a teleport
+        if self.teleports_component.jump().is_none() {
+            self.player_component.draw(
+                &mut canvas,
+                DrawParam::new(),
+                PlayerComponentParam::new(self.camera, self.lighting.tint()),
+            )?;
+        }
+
+        // Print the grid component, placing its labels for the current view first.
+        self.grid_component.update(GridComponentUpdateParam::new(
+            GridComponentUpdateType::View(
+                self.camera,
+                vec2(
+                    ctx.gfx.window().inner_size().width as f32,
+                    ctx.gfx.window().inner_size().height as f32,
+                ),
+            ),
+        ))?;
+        self.grid_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            GridComponentParam::new(self.camera),
+        )?;
+
+        // Print the weather particles component
+        self.particles_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            ParticlesComponentParam::new(
+                self.camera.origin(),
+                vec2(
+                    ctx.gfx.window().inner_size().width as f32,
+                    ctx.gfx.window().inner_size().height as f32,
+                ),
+            ),
+        )?;
+
+        // Print the inspector component
+        self.inspector_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            InspectorComponentParam::new(self.camera, self.cursor),
+        )?;
+
+        // Print the selection component
+        self.selection_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            SelectionComponentParam::new(self.camera),
+        )?;
+
+        // The panels are drawn on a screen-space pass, which doesn't move with the camera and
+        // is measured in logical pixels, so they keep their size on HiDPI displays.
+        self.hud_component.fit(ctx);
+        let layout = self.layout(ctx);
+        canvas.set_screen_coordinates(layout.screen_coordinates());
+
+        // Print the score component
+        self.score_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            ScoreComponentParam::new(layout),
+        )?;
+
+        // Print the legend of the elevation component
+        self.elevation_component
+            .draw_legend(&mut canvas, DrawParam::new(), &layout);
+
+        // Print the panel of the selection component
+        self.selection_component
+            .draw_panel(&mut canvas, DrawParam::new(), &layout);
+
+        // Print the filter component
+        self.filter_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            FilterComponentParam::new(layout),
+        )?;
+
+        // Print the legend component
+        self.legend_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            LegendComponentParam::new(layout),
         )?;
 
         // Print the dialog component
         self.dialog_component.draw(
             &mut canvas,
             DrawParam::new(),
-            DialogComponentParam::new(self.origin),
+            DialogComponentParam::new(layout),
         )?;
 
+        // Print the HUD component
+        self.hud_component.draw(
+            &mut canvas,
+            DrawParam::new(),
+            HudComponentParam::new(layout),
+        )?;
+
+        // Print the tooltip of the inspector component, on top of the panels
+        self.inspector_component
+            .draw_tooltip(&mut canvas, DrawParam::new(), &layout, self.cursor);
+
         // Render the components on the canvas.
         canvas.finish(&mut ctx.gfx)?;
 
         Ok(())
     }
 
-    /// Add zooming to the visualizer.
+    /// Add zooming to the visualizer, keeping the center of the window fixed.
     pub fn add_scale(&mut self, gfx: &impl Has<GraphicsContext>, scale: f32) {
-        if self.scale + scale * 0.01 > 0.5 && self.scale + scale * 0.01 < 6.0 {
-            let screen_width = gfx.retrieve().window().inner_size().width as f32;
-            let screen_height = gfx.retrieve().window().inner_size().height as f32;
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        self.add_scale_at(scale, vec2(screen_width * 0.5, screen_height * 0.5));
+    }
+
+    /// Add zooming to the visualizer, keeping the given point of the window fixed.
+    pub fn add_scale_at(&mut self, scale: f32, focus: Vec2) {
+        // A step crossing a bound of the zoom stops on it.
+        let new_scale = (self.camera.scale() + scale * 0.01).clamp(ZOOM.0, ZOOM.1);
+
+        self.camera.zoom_at(new_scale, focus);
+    }
+
+    /// Restores the initial zoom of the visualizer, keeping the center of the window fixed.
+    pub fn reset_scale(&mut self, gfx: &impl Has<GraphicsContext>) {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        self.camera.zoom_at(
+            self.initial_scale,
+            vec2(screen_width * 0.5, screen_height * 0.5),
+        );
+    }
+
+    /// Centers the visualizer on the robot.
+    pub fn recenter(&mut self) {
+        let (row, col) = self.player_component.position();
+
+        self.set_center(vec2(col as f32, row as f32));
+    }
 
-            let offset_x =
-                (screen_width * 0.5 - self.origin.x) / (16.0 * self.map_size.x as f32 * self.scale);
-            let offset_y =
-                (screen_height * 0.5 - self.origin.y) / (8.0 * self.map_size.y as f32 * self.scale);
+    /// Advances the animations of the visualizer by the given elapsed seconds.
+    pub fn animate(&mut self, gfx: &impl Has<GraphicsContext>, delta: f32) -> GameResult {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        self.elapsed += delta;
 
-            self.origin.x += self.map_size.x * scale * 0.01 * 8.0 * offset_x;
-            self.origin.y += self.map_size.y * scale * 0.01 * 4.0 * offset_y;
+        self.teleports_component
+            .update(TeleportsComponentUpdateParam::new(
+                TeleportsComponentUpdateType::Tick(delta),
+            ))?;
 
-            self.scale += scale * 0.01;
+        self.player_component
+            .update(PlayerComponentUpdateParam::new(
+                PlayerComponentUpdateType::Tick(delta),
+            ))?;
+
+        // The camera follows the robot while it jumps through a teleport.
+        if let Some(center) = self.teleports_component.jump() {
+            self.follow(gfx, center);
+        }
+
+        // The camera eases toward its target center, slowing down as it gets closer.
+        if let Some(target) = self.center_target {
+            let target = self.center_origin(gfx, target);
+
+            let origin = self.camera.origin();
+
+            if origin.distance(target) < 0.5 {
+                self.camera.set_origin(target);
+                self.center_target = None;
+            } else {
+                self.camera
+                    .set_origin(origin.lerp(target, 1.0 - (-CAMERA_EASING * delta).exp()));
+            }
+        }
+
+        self.particles_component
+            .update(ParticlesComponentUpdateParam::new(
+                ParticlesComponentUpdateType::Tick(delta, vec2(screen_width, screen_height)),
+            ))?;
+
+        // Finds the tile under the mouse cursor, converting the cursor into world coordinates.
+        let hovered = self
+            .cursor
+            .and_then(|cursor| {
+                Camera::tile_at(
+                    &self.map_rc.borrow(),
+                    self.camera.orientation(),
+                    self.camera.screen_to_world(cursor),
+                )
+            })
+            .map(|coords| {
+                let (tile, discovered) = self.tile(coords);
+
+                HoveredTile::new(coords, tile, discovered)
+            });
+
+        self.inspector_component
+            .update(InspectorComponentUpdateParam::new(hovered))?;
+
+        Ok(())
+    }
+
+    /// The function returns the tile in the given coordinates and whether it's known by the
+    /// robot. It prefers the tile known by the robot, which is the most recent one.
+    fn tile(&self, (row, col): (usize, usize)) -> (Tile, bool) {
+        let known = self
+            .world_rc
+            .borrow()
+            .as_ref()
+            .and_then(|world| world[row][col].clone());
+
+        match known {
+            Some(tile) => (tile, true),
+            None => (self.map_rc.borrow()[row][col].clone(), false),
         }
     }
 
+    /// Sets the position of the mouse cursor in the window, or None if it left the window.
+    pub fn set_cursor(&mut self, cursor: Option<Vec2>) {
+        self.cursor = cursor;
+    }
+
+    /// Enables or disables the weather particles.
+    pub fn toggle_particles(&mut self) -> GameResult {
+        let enabled = self.particles_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Weather effects {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Shows or hides the coordinate grid.
+    pub fn toggle_grid(&mut self) -> GameResult {
+        let enabled = self.grid_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Coordinate grid {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Shows or hides the trail of the robot.
+    pub fn toggle_trail(&mut self) -> GameResult {
+        let enabled = self.trail_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Robot trail {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Changes the number of steps of the robot trail by the given amount.
+    pub fn add_trail_length(&mut self, amount: isize) -> GameResult {
+        let length = self.trail_component.length().saturating_add_signed(amount);
+        self.trail_component.set_length(length);
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Robot trail length is {}",
+                self.trail_component.length()
+            )))
+    }
+
+    /// Cycles the heatmap between entered tiles, spent ticks and hidden.
+    pub fn cycle_heatmap(&mut self) -> GameResult {
+        let text = match self.heatmap_component.cycle_metric() {
+            Some(HeatmapMetric::Visits) => "Heatmap shows how many times tiles were entered",
+            Some(HeatmapMetric::Ticks) => "Heatmap shows how many ticks were spent on tiles",
+            None => "Heatmap disabled",
+        };
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(text.to_string()))
+    }
+
+    /// Switches between the textured view and the elevation view of the map.
+    pub fn toggle_elevation(&mut self) -> GameResult {
+        let enabled = self.elevation_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Elevation view {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Cycles the fog of war between dimmed, recently seen, known only and omniscient.
+    pub fn cycle_fog(&mut self) -> GameResult {
+        let mode = self.fog_rc.borrow_mut().cycle_mode();
+
+        self.tiles_map_component.refresh_fog();
+        self.contents_map_component.refresh_fog();
+        self.grid_component.refresh_fog();
+
+        let text = match mode {
+            FogMode::Omniscient => "Fog of war disabled, the whole world is shown",
+            FogMode::KnownOnly => "Fog of war hides the tiles unknown to the robot",
+            FogMode::Dimmed => "Fog of war dims the tiles unknown to the robot",
+            FogMode::RecentlySeen => "Fog of war fades the tiles not seen recently",
+        };
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(text.to_string()))
+    }
+
+    /// Shows or hides the panel of the content filter.
+    pub fn toggle_filter(&mut self) -> GameResult {
+        let enabled = self.filter_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Content filter {}",
+                if enabled { "shown" } else { "hidden" }
+            )))
+    }
+
+    /// Shows or hides the outline of the exploration frontier.
+    pub fn toggle_frontier(&mut self) -> GameResult {
+        let enabled = self.frontier_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Exploration frontier {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Shows or hides the network of teleports.
+    pub fn toggle_teleports(&mut self) -> GameResult {
+        let enabled = self.teleports_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Teleport network {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Shows or hides the preview of the spotlight and the tiles discovered in the last tick.
+    pub fn toggle_spotlight(&mut self) -> GameResult {
+        let enabled = self.spotlight_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Spotlight preview {}",
+                if enabled { "enabled" } else { "disabled" }
+            )))
+    }
+
+    /// Sets the distance of the spotlight preview, which should match the one used by the robot.
+    pub fn set_spotlight_distance(&mut self, distance: usize) {
+        self.spotlight_component.set_distance(distance);
+    }
+
+    /// Changes the distance of the spotlight preview by the given amount.
+    pub fn add_spotlight_distance(&mut self, amount: isize) -> GameResult {
+        let distance = self
+            .spotlight_component
+            .distance()
+            .saturating_add_signed(amount);
+        self.spotlight_component.set_distance(distance);
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Spotlight preview distance is {}",
+                distance
+            )))
+    }
+
+    /// Shows or hides the map legend.
+    pub fn toggle_legend(&mut self) -> GameResult {
+        let enabled = self.legend_component.toggle();
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Map legend {}",
+                if enabled { "shown" } else { "hidden" }
+            )))
+    }
+
+    /// Handles a click of the mouse in the given point of the window.
+    pub fn click(&mut self, gfx: &impl Has<GraphicsContext>, point: Vec2) -> GameResult {
+        // The panels are placed in logical pixels, while the point is in physical ones.
+        let layout = self.layout(gfx);
+        let panel_point = layout.to_logical(point);
+
+        // Clicks on the entries of the panels don't select tiles.
+        let on_panel = self
+            .legend_component
+            .entry_at(panel_point, &layout)
+            .is_some()
+            || self
+                .filter_component
+                .entry_at(panel_point, &layout)
+                .is_some();
+
+        // Shows or hides the textures of the legend entry under the cursor.
+        if let Some(textures) = self.legend_component.entry_at(panel_point, &layout) {
+            let visible = self.legend_component.toggle_textures(&textures);
+
+            self.tiles_map_component
+                .set_hidden(self.legend_component.hidden());
+            self.contents_map_component
+                .update(ContentsMapComponentUpdateParam::new(
+                    ContentsMapComponentUpdateType::Visibility(self.legend_component.hidden()),
+                ))?;
+
+            self.dialog_component
+                .update(DialogComponentUpdateParam::new(format!(
+                    "{} {}",
+                    textures
+                        .iter()
+                        .map(|texture| texture.name())
+                        .collect::<Vec<_>>()
+                        .join(" and "),
+                    if visible { "shown" } else { "hidden" }
+                )))?;
+        }
+
+        // Selects the content kind under the cursor, highlighting its tiles.
+        if let Some(texture) = self.filter_component.entry_at(panel_point, &layout) {
+            let selected = self.filter_component.select(texture);
+
+            self.contents_map_component
+                .update(ContentsMapComponentUpdateParam::new(
+                    ContentsMapComponentUpdateType::Filter(self.filter_component.selected()),
+                ))?;
+
+            self.dialog_component
+                .update(DialogComponentUpdateParam::new(format!(
+                    "{} {} the content filter",
+                    texture.name(),
+                    if selected { "added to" } else { "removed from" }
+                )))?;
+        }
+
+        // Selects the tile under the cursor on a double click.
+        let double_click = self.last_click.is_some_and(|(time, last_point)| {
+            self.elapsed - time < DOUBLE_CLICK_TIME && last_point.distance(point) < 5.0
+        });
+
+        if on_panel {
+            self.last_click = None;
+        } else if double_click {
+            self.last_click = None;
+            self.select_at(point)?;
+        } else {
+            self.last_click = Some((self.elapsed, point));
+        }
+
+        Ok(())
+    }
+
+    /// Selects the tile under the cursor, or under the center of the window when the cursor is
+    /// outside of it, as happens with a gamepad.
+    pub fn select(&mut self, gfx: &impl Has<GraphicsContext>) -> GameResult {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        self.select_at(
+            self.cursor
+                .unwrap_or(vec2(screen_width * 0.5, screen_height * 0.5)),
+        )
+    }
+
+    /// The function selects the tile under the given point of the window, centering the camera
+    /// on it. Selecting the selected tile again clears the selection.
+    /// The camera stops following the robot while a tile is selected.
+    fn select_at(&mut self, point: Vec2) -> GameResult {
+        let Some(coords) = Camera::tile_at(
+            &self.map_rc.borrow(),
+            self.camera.orientation(),
+            self.camera.screen_to_world(point),
+        ) else {
+            return Ok(());
+        };
+
+        if self.selection_component.selected() == Some(coords) {
+            self.selection_component
+                .update(SelectionComponentUpdateParam::new(
+                    SelectionComponentUpdateType::Cleared,
+                ))?;
+
+            return self
+                .dialog_component
+                .update(DialogComponentUpdateParam::new(format!(
+                    "Tile {:?} deselected",
+                    coords
+                )));
+        }
+
+        let (tile, discovered) = self.tile(coords);
+
+        self.selection_component
+            .update(SelectionComponentUpdateParam::new(
+                SelectionComponentUpdateType::Selected(coords, tile, discovered),
+            ))?;
+
+        self.set_center(vec2(coords.1 as f32, coords.0 as f32));
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Tile {:?} selected, the camera is pinned on it",
+                coords
+            )))
+    }
+
     /// Moves the visualizer adding an offset to the origin.
     pub fn add_offset(&mut self, offset: Vec2) {
-        self.origin.x += offset.x;
-        self.origin.y -= offset.y;
+        // Moving the camera by hand stops it from easing toward its target.
+        if offset != Vec2::ZERO {
+            self.center_target = None;
+        }
+
+        self.camera.pan(vec2(offset.x, -offset.y));
+    }
+
+    /// The function moves the center of the visualizer toward the given tile_center, easing the
+    /// camera over the next frames.
+    pub fn set_center(&mut self, tile_center: Vec2) {
+        self.center_target = Some(tile_center);
+    }
+
+    /// The function sets the center of the visualizer to the given tile_center instantly.
+    pub fn set_center_now(&mut self, gfx: &impl Has<GraphicsContext>, tile_center: Vec2) {
+        self.center_target = None;
+        self.camera.set_origin(self.center_origin(gfx, tile_center));
+    }
+
+    /// The function returns the origin which centers the visualizer on the given tile_center.
+    fn center_origin(&self, gfx: &impl Has<GraphicsContext>, tile_center: Vec2) -> Vec2 {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        self.camera.centered_on(
+            self.image_center(tile_center),
+            vec2(screen_width, screen_height),
+        )
+    }
+
+    /// The function returns the tile_center in the center of the window, as if the tile there
+    /// were a block.
+    fn window_center(&self, gfx: &impl Has<GraphicsContext>) -> Vec2 {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        let center = self
+            .camera
+            .screen_to_world(vec2(screen_width * 0.5, screen_height * 0.5))
+            - Camera::face_center();
+        let (row, col) =
+            Camera::world_to_tile(self.map_size.x as usize, self.camera.orientation(), center);
+
+        vec2(col, row)
+    }
+
+    /// The function returns the center of the top face of a block in the given tile_center, in
+    /// world coordinates.
+    fn image_center(&self, tile_center: Vec2) -> Vec2 {
+        Camera::tile_to_world(
+            self.map_size.x as usize,
+            self.camera.orientation(),
+            tile_center.y,
+            tile_center.x,
+        ) + Camera::face_center()
+    }
+
+    /// The function moves the camera on the given tile_center, according to the follow mode.
+    fn follow(&mut self, gfx: &impl Has<GraphicsContext>, tile_center: Vec2) {
+        let screen_width = gfx.retrieve().window().inner_size().width as f32;
+        let screen_height = gfx.retrieve().window().inner_size().height as f32;
+
+        // The robot is considered outside of the window when it's near its edges.
+        let margin = screen_width.min(screen_height) * 0.1;
+        let position = self.camera.world_to_screen(self.image_center(tile_center));
+        let outside = position.x < margin
+            || position.y < margin
+            || position.x > screen_width - margin
+            || position.y > screen_height - margin;
+
+        // The camera stays pinned on the selected tile.
+        if self.selection_component.selected().is_some() {
+            return;
+        }
+
+        match self.follow_mode {
+            FollowMode::Always => self.set_center(tile_center),
+            FollowMode::Viewport if outside => self.set_center(tile_center),
+            FollowMode::Viewport | FollowMode::Free => {}
+        }
+    }
+
+    /// Rotates the view clockwise by 90 degrees, keeping the same point of the map in the center
+    /// of the window.
+    pub fn rotate(&mut self, gfx: &impl Has<GraphicsContext>) -> GameResult {
+        let center = self.window_center(gfx);
+        let orientation = self.camera.rotate();
+
+        // Every component placing the tiles in world coordinates follows the new orientation.
+        self.tiles_map_component.set_orientation(gfx, orientation);
+        self.contents_map_component
+            .set_orientation(gfx, orientation);
+        self.elevation_component.set_orientation(orientation);
+        self.grid_component.set_orientation(orientation);
+        self.heatmap_component.set_orientation(orientation);
+        self.spotlight_component.set_orientation(orientation);
+        self.frontier_component.set_orientation(orientation);
+        self.trail_component.set_orientation(orientation);
+        self.teleports_component.set_orientation(orientation);
+        self.player_component.set_orientation(orientation);
+
+        // Keeps easing toward the target center, if any, which is in tile coordinates.
+        let target = self.center_target;
+        self.set_center_now(gfx, center);
+        self.center_target = target;
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "View rotated, facing {}",
+                orientation.name()
+            )))
     }
 
-    /// The function sets the center of the visualizer to the given tile_center.
-    pub fn set_center(&mut self, gfx: &impl Has<GraphicsContext>, tile_center: Vec2) {
-        let x = tile_center.x;
-        let y = tile_center.y;
+    /// Returns the tile_center and the zoom of the camera, to be bookmarked. While the camera is
+    /// moving, the tile_center is the one it's moving to.
+    pub fn bookmark(&self, gfx: &impl Has<GraphicsContext>) -> (Vec2, f32) {
+        let tile_center = self
+            .center_target
+            .unwrap_or_else(|| self.window_center(gfx));
 
+        (tile_center, self.camera.scale())
+    }
+
+    /// Moves the camera to the given bookmark, restoring its zoom. The camera stops following
+    /// the robot, so it stays on the bookmark.
+    pub fn show_bookmark(
+        &mut self,
+        gfx: &impl Has<GraphicsContext>,
+        bookmark: &Bookmark,
+    ) -> GameResult {
         let screen_width = gfx.retrieve().window().inner_size().width as f32;
         let screen_height = gfx.retrieve().window().inner_size().height as f32;
 
-        let image_x = (16.0 * 0.5) * (self.map_size.y - y + x - 1.0) as f32;
-        let image_y = 3.75 * (x + y) as f32;
+        // Bookmarks can be edited by hand, so the zoom is kept within its usual limits.
+        self.camera.zoom_at(
+            bookmark.scale.clamp(ZOOM.0, ZOOM.1),
+            vec2(screen_width * 0.5, screen_height * 0.5),
+        );
+        self.set_center(bookmark.tile_center);
+
+        if self.follow_mode != FollowMode::Free {
+            self.follow_mode = FollowMode::Free;
+
+            self.hud_component.update(HudComponentUpdateParam::new(
+                HudComponentUpdateType::FollowMode(self.follow_mode),
+            ))?;
+        }
 
-        self.origin.x =
-            (-image_x * self.scale + screen_width * 0.5 - 16.0 * 0.5 * self.scale) * -1.0;
-        self.origin.y =
-            (-image_y * self.scale + screen_height * 0.5 - 4.0 * 0.5 * self.scale) * -1.0;
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(format!(
+                "Showing {}",
+                bookmark.name
+            )))
+    }
+
+    /// Cycles between the modes of the camera: following the robot, following it only when it
+    /// leaves the window, or never following it.
+    pub fn cycle_follow_mode(&mut self) -> GameResult {
+        self.follow_mode = self.follow_mode.next();
+
+        self.hud_component.update(HudComponentUpdateParam::new(
+            HudComponentUpdateType::FollowMode(self.follow_mode),
+        ))?;
+
+        let text = match self.follow_mode {
+            FollowMode::Always => "Camera follows the robot",
+            FollowMode::Viewport => "Camera follows the robot when it leaves the window",
+            FollowMode::Free => "Camera is free",
+        };
+
+        self.dialog_component
+            .update(DialogComponentUpdateParam::new(text.to_string()))
     }
 
     /// The functions runs the next tick of the game.
+    /// The events of the previous tick have all been handled at this point, so the components
+    /// sample its results before running the next one. Sampling never fails.
     pub fn next_tick(&mut self) -> Result<(), LibError> {
-        self.runner.game_tick()
+        self.score_component
+            .update(ScoreComponentUpdateParam::new(
+                ScoreComponentUpdateType::Tick,
+            ))
+            .ok();
+
+        self.trail_component
+            .update(TrailComponentUpdateParam::new(
+                TrailComponentUpdateType::Tick,
+            ))
+            .ok();
+
+        self.heatmap_component
+            .update(HeatmapComponentUpdateParam::new(
+                HeatmapComponentUpdateType::Tick,
+            ))
+            .ok();
+
+        self.selection_component
+            .update(SelectionComponentUpdateParam::new(
+                SelectionComponentUpdateType::Tick,
+            ))
+            .ok();
+
+        self.fog_rc.borrow_mut().advance();
+
+        // The world is shared only after the first tick, with the tiles known since the spawn.
+        let spawned = self.world_rc.borrow().is_none();
+
+        let result = self.runner.game_tick();
+
+        // The tick has changed the world known by the robot, so the map follows it once, before
+        // the events of the tick are handled.
+        self.sync_world(spawned).ok();
+
+        result
     }
 
-    /// The function updates the visualizer's components with the current state of the world.
-    /// It also pops the events from the event_queue and updates the visualizer's state.
-    /// If visualizer doesn't have any knowledge about the robot's known tiles, it will hide them
-    /// all.
-    pub fn handle_event(&mut self, gfx: &impl Has<GraphicsContext>) -> GameResult {
-        while let Some(event) = self.event_queue().borrow_mut().pop_front() {
-            self.tiles_map_component
-                .update(TilesMapComponentUpdateParam::new(
-                    self.world_rc.borrow().clone().unwrap_or(vec![
-                        vec![
-                            None;
-                            self.map_size.x as usize
-                        ];
-                        self.map_size.y as usize
-                    ]),
+    /// The function updates the map with the world known by the robot and colors the tiles
+    /// whose fog has changed. The tiles known since the spawn, observed by the first sync,
+    /// aren't highlighted as discovered.
+    fn sync_world(&mut self, spawned: bool) -> GameResult {
+        let world = self.world_rc.borrow();
+        let Some(world) = world.as_deref() else {
+            return Ok(());
+        };
+
+        let discovered = self.fog_rc.borrow_mut().observe(world);
+
+        self.tiles_map_component
+            .update(TilesMapComponentUpdateParam::new(world))?;
+
+        self.selection_component
+            .update(SelectionComponentUpdateParam::new(
+                SelectionComponentUpdateType::Changed(
+                    &discovered,
+                    self.tiles_map_component.changes(),
+                    world,
+                ),
+            ))?;
+
+        self.grid_component.update(GridComponentUpdateParam::new(
+            GridComponentUpdateType::Changed(self.tiles_map_component.changes()),
+        ))?;
+
+        // The overlays scanning the world follow it only when the tick has changed it.
+        if !discovered.is_empty() || !self.tiles_map_component.changes().is_empty() {
+            self.frontier_component
+                .update(FrontierComponentUpdateParam::new(
+                    FrontierComponentUpdateType::WorldVisibility(world),
                 ))?;
 
-            self.contents_map_component
-                .update(ContentsMapComponentUpdateParam::new(
-                    ContentsMapComponentUpdateType::WorldVisibility(
-                        self.world_rc.borrow().clone().unwrap_or(vec![
-                            vec![
-                                None;
-                                self.map_size.x as usize
-                            ];
-                            self.map_size.y as usize
-                        ]),
-                    ),
+            self.hud_component.update(HudComponentUpdateParam::new(
+                HudComponentUpdateType::Frontier(self.frontier_component.size()),
+            ))?;
+
+            self.teleports_component
+                .update(TeleportsComponentUpdateParam::new(
+                    TeleportsComponentUpdateType::WorldVisibility(world),
+                ))?;
+
+            self.legend_component
+                .update(LegendComponentUpdateParam::new(
+                    LegendComponentUpdateType::WorldVisibility(world),
                 ))?;
 
+            self.filter_component
+                .update(FilterComponentUpdateParam::new(
+                    FilterComponentUpdateType::WorldVisibility(world),
+                ))?;
+        }
+
+        self.spotlight_component
+            .update(SpotlightComponentUpdateParam::new(
+                SpotlightComponentUpdateType::Discovered(if spawned { &[] } else { &discovered }),
+            ))?;
+
+        let changed = self.fog_rc.borrow_mut().take_changed();
+        self.tiles_map_component.refresh_fog_at(&changed);
+        self.contents_map_component.refresh_fog_at(&changed);
+        self.grid_component.refresh_fog_at(&changed);
+
+        if !discovered.is_empty() {
+            self.elevation_component.update(())?;
+        }
+
+        Ok(())
+    }
+
+    /// The function pops the events from the event_queue and updates the visualizer's state.
+    /// The map has already followed the world known by the robot at the end of the tick.
+    pub fn handle_event(&mut self, gfx: &impl Has<GraphicsContext>) -> GameResult {
+        while let Some(event) = self.event_queue().borrow_mut().pop_front() {
             match event {
                 Event::Ready => {
                     self.dialog_component
@@ -240,6 +1249,19 @@ impl Visualizer {
                             "Time has changed. It's {}",
                             environment.get_time_of_day_string()
                         )))?;
+
+                    self.lighting = Lighting::from_environment(&environment);
+
+                    self.particles_component
+                        .update(ParticlesComponentUpdateParam::new(
+                            ParticlesComponentUpdateType::WeatherChanged(
+                                environment.get_weather_condition(),
+                            ),
+                        ))?;
+
+                    self.hud_component.update(HudComponentUpdateParam::new(
+                        HudComponentUpdateType::TimeChanged(environment),
+                    ))?;
                     break;
                 }
                 Event::DayChanged(day) => {
@@ -248,6 +1270,19 @@ impl Visualizer {
                             "The day has changed. Now is {:?}",
                             day
                         )))?;
+
+                    self.lighting = Lighting::from_environment(&day);
+
+                    self.particles_component
+                        .update(ParticlesComponentUpdateParam::new(
+                            ParticlesComponentUpdateType::WeatherChanged(
+                                day.get_weather_condition(),
+                            ),
+                        ))?;
+
+                    self.hud_component.update(HudComponentUpdateParam::new(
+                        HudComponentUpdateType::DayChanged(day),
+                    ))?;
                     break;
                 }
                 Event::EnergyRecharged(energy) => {
@@ -257,9 +1292,42 @@ impl Visualizer {
                             energy
                         )))?;
                 }
-                Event::Moved(_tile, coords) => {
+                Event::Moved(tile, coords) => {
+                    self.fog_rc.borrow_mut().move_to(coords);
+
+                    // The robot moves over the time passed since its previous movement.
+                    let duration =
+                        (self.elapsed - self.last_moved).clamp(MOVE_DURATION.0, MOVE_DURATION.1);
+                    self.last_moved = self.elapsed;
+
                     self.player_component
-                        .update(PlayerComponentUpdateParam::new(coords))?;
+                        .update(PlayerComponentUpdateParam::new(
+                            PlayerComponentUpdateType::Moved(tile.clone(), coords, duration),
+                        ))?;
+
+                    self.heatmap_component
+                        .update(HeatmapComponentUpdateParam::new(
+                            HeatmapComponentUpdateType::Moved(tile.clone(), coords),
+                        ))?;
+
+                    self.teleports_component
+                        .update(TeleportsComponentUpdateParam::new(
+                            TeleportsComponentUpdateType::Moved(tile.clone(), coords),
+                        ))?;
+
+                    self.spotlight_component
+                        .update(SpotlightComponentUpdateParam::new(
+                            SpotlightComponentUpdateType::Moved(coords),
+                        ))?;
+
+                    self.trail_component.update(TrailComponentUpdateParam::new(
+                        TrailComponentUpdateType::Moved(tile, coords),
+                    ))?;
+
+                    self.selection_component
+                        .update(SelectionComponentUpdateParam::new(
+                            SelectionComponentUpdateType::Moved(coords),
+                        ))?;
 
                     self.dialog_component
                         .update(DialogComponentUpdateParam::new(format!(
@@ -267,7 +1335,7 @@ impl Visualizer {
                             coords
                         )))?;
 
-                    self.set_center(gfx, vec2(coords.1 as f32, coords.0 as f32));
+                    self.follow(gfx, vec2(coords.1 as f32, coords.0 as f32));
                     break;
                 }
                 Event::TileContentUpdated(tile, coords) => {
@@ -277,28 +1345,37 @@ impl Visualizer {
                             &tile.tile_type, coords
                         )))?;
 
-                    self.contents_map_component
-                        .update(ContentsMapComponentUpdateParam::new(
-                            ContentsMapComponentUpdateType::ContentChange(tile, coords),
-                        ))?;
+                    self.contents_map_component.set_content(gfx, &tile, coords);
+
+                    self.score_component.update(ScoreComponentUpdateParam::new(
+                        ScoreComponentUpdateType::TileContentUpdated(tile.content),
+                    ))?;
 
                     break;
                 }
-                Event::AddedToBackpack(content, _count) => {
+                Event::AddedToBackpack(content, count) => {
                     self.dialog_component
                         .update(DialogComponentUpdateParam::new(format!(
                             "The content {:?} has been added to the backpack",
                             content
                         )))?;
 
+                    self.score_component.update(ScoreComponentUpdateParam::new(
+                        ScoreComponentUpdateType::AddedToBackpack(content, count),
+                    ))?;
+
                     break;
                 }
-                Event::RemovedFromBackpack(content, _count) => {
+                Event::RemovedFromBackpack(content, count) => {
                     self.dialog_component
                         .update(DialogComponentUpdateParam::new(format!(
                             "The content {:?} has been removed from the backpack",
                             content
                         )))?;
+
+                    self.score_component.update(ScoreComponentUpdateParam::new(
+                        ScoreComponentUpdateType::RemovedFromBackpack(content, count),
+                    ))?;
                     break;
                 }
                 _ => {}
@@ -308,11 +1385,6 @@ impl Visualizer {
         Ok(())
     }
 
-    /// The function returns the reference to the origin of the visualizer.
-    pub fn origin(&self) -> Vec2 {
-        self.origin
-    }
-
     /// The function returns the shared reference to the event_queue of the visualizer.
     pub fn event_queue(&self) -> Rc<RefCell<VecDeque<Event>>> {
         self.event_queue_rc.clone()
@@ -2,7 +2,10 @@ use ggez::{
     context::Has,
     graphics::{GraphicsContext, Image},
 };
-use robotics_lib::world::tile::{Content, Tile, TileType};
+use robotics_lib::world::{
+    environmental_conditions::WeatherType,
+    tile::{Content, Tile, TileType},
+};
 
 #[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
 pub enum Texture {
@@ -43,6 +46,13 @@ pub enum Texture {
     Scarecrow,
     JollyBlock,
     Player,
+    Sunny,
+    Rainy,
+    Foggy,
+    TropicalMonsoon,
+    TrentinoSnow,
+    Grid,
+    Highlight,
 }
 
 impl Texture {
@@ -149,6 +159,16 @@ impl Texture {
         }
     }
 
+    pub fn from_weather(weather: &WeatherType) -> Self {
+        match weather {
+            WeatherType::Sunny => Texture::Sunny,
+            WeatherType::Rainy => Texture::Rainy,
+            WeatherType::Foggy => Texture::Foggy,
+            WeatherType::TropicalMonsoon => Texture::TropicalMonsoon,
+            WeatherType::TrentinoSnow => Texture::TrentinoSnow,
+        }
+    }
+
     pub fn get_image(&self, gfx: &impl Has<GraphicsContext>) -> Image {
         match self {
             Texture::SandBlock => Image::from_path(gfx, "/blocks/sand.png").unwrap(),
@@ -190,6 +210,15 @@ impl Texture {
             Texture::Building => Image::from_path(gfx, "/contents/building.png").unwrap(),
             Texture::Scarecrow => Image::from_path(gfx, "/contents/scarecrow.png").unwrap(),
             Texture::JollyBlock => Image::from_path(gfx, "/contents/jollyblock.png").unwrap(),
+            Texture::Sunny => Image::from_path(gfx, "/weather/sunny.png").unwrap(),
+            Texture::Rainy => Image::from_path(gfx, "/weather/rainy.png").unwrap(),
+            Texture::Foggy => Image::from_path(gfx, "/weather/foggy.png").unwrap(),
+            Texture::TropicalMonsoon => {
+                Image::from_path(gfx, "/weather/tropicalmonsoon.png").unwrap()
+            }
+            Texture::TrentinoSnow => Image::from_path(gfx, "/weather/trentinosnow.png").unwrap(),
+            Texture::Grid => Image::from_path(gfx, "/overlays/grid.png").unwrap(),
+            Texture::Highlight => Image::from_path(gfx, "/overlays/tile.png").unwrap(),
         }
     }
 
@@ -220,6 +249,84 @@ impl Texture {
         ]
     }
 
+    pub fn get_contents() -> Vec<Self> {
+        vec![
+            Self::Fish,
+            Self::Tree,
+            Self::Bush,
+            Self::Rock,
+            Self::Fire,
+            Self::Garbage,
+            Self::Coin,
+            Self::Bin,
+            Self::Crate,
+            Self::Market,
+            Self::Bank,
+            Self::Building,
+            Self::Scarecrow,
+            Self::JollyBlock,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Texture::SandBlock => "Sand",
+            Texture::SandHalf => "Sand (half)",
+            Texture::HillBlock => "Hill",
+            Texture::HillHalf => "Hill (half)",
+            Texture::SnowBlock => "Snow",
+            Texture::SnowHalf => "Snow (half)",
+            Texture::LavaBlock => "Lava",
+            Texture::LavaHalf => "Lava (half)",
+            Texture::WallBlock => "Wall",
+            Texture::WallHalf => "Wall (half)",
+            Texture::GrassBlock => "Grass",
+            Texture::GrassHalf => "Grass (half)",
+            Texture::StreetBlock => "Street",
+            Texture::StreetHalf => "Street (half)",
+            Texture::MountainBlock => "Mountain",
+            Texture::MountainHalf => "Mountain (half)",
+            Texture::DeepWaterBlock => "Deep water",
+            Texture::DeepWaterHalf => "Deep water (half)",
+            Texture::ShallowWaterBlock => "Shallow water",
+            Texture::ShallowWaterHalf => "Shallow water (half)",
+            Texture::TeleportBlock => "Teleport",
+            Texture::TeleportHalf => "Teleport (half)",
+            Texture::Fish => "Fish",
+            Texture::Tree => "Tree",
+            Texture::Bush => "Bush",
+            Texture::Rock => "Rock",
+            Texture::Fire => "Fire",
+            Texture::Garbage => "Garbage",
+            Texture::Coin => "Coin",
+            Texture::Bin => "Bin",
+            Texture::Crate => "Crate",
+            Texture::Market => "Market",
+            Texture::Bank => "Bank",
+            Texture::Building => "Building",
+            Texture::Scarecrow => "Scarecrow",
+            Texture::JollyBlock => "Jolly block",
+            Texture::Player => "Robot",
+            Texture::Sunny => "Sunny",
+            Texture::Rainy => "Rainy",
+            Texture::Foggy => "Foggy",
+            Texture::TropicalMonsoon => "Tropical monsoon",
+            Texture::TrentinoSnow => "Trentino snow",
+            Texture::Grid => "Grid",
+            Texture::Highlight => "Highlight",
+        }
+    }
+
+    pub fn get_weathers() -> Vec<Self> {
+        vec![
+            Self::Sunny,
+            Self::Rainy,
+            Self::Foggy,
+            Self::TropicalMonsoon,
+            Self::TrentinoSnow,
+        ]
+    }
+
     pub fn width() -> f32 {
         16.0
     }